@@ -0,0 +1,130 @@
+use crate::{ConfigCommand, ConfigCommands};
+use golta_core::config::{config_path, load_config, save_config, AutoInstall};
+use golta_core::pinned_version::find_project_file;
+use golta_core::project_file::{ProjectFile, PROJECT_FILE_NAME};
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+pub fn run(cmd: ConfigCommand) {
+    let mut stdout = io::stdout();
+    let result = match cmd.command {
+        ConfigCommands::Validate { path } => validate(path.as_deref(), &mut stdout),
+        ConfigCommands::AutoInstall { policy } => home::home_dir()
+            .ok_or_else(|| "Could not find home directory".into())
+            .and_then(|home| auto_install(&home, policy, &mut stdout)),
+    };
+
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+/// Checks `path`, or the `.golta.json` that applies to the current directory, against the
+/// project file schema.
+fn validate(path: Option<&Path>, writer: &mut impl Write) -> Result<(), Box<dyn Error>> {
+    let (project, path) = match path {
+        Some(path) => (read_project_file(path)?, path.to_path_buf()),
+        None => find_project_file(&env::current_dir()?)?.ok_or_else(|| {
+            format!(
+                "No {} found in this directory or its parents.",
+                PROJECT_FILE_NAME
+            )
+        })?,
+    };
+
+    writeln!(writer, "{} is valid.", path.display())?;
+    if project.is_empty() {
+        writeln!(
+            writer,
+            "It does not pin any version or set any environment."
+        )?;
+    }
+    Ok(())
+}
+
+/// Prints the `auto_install` setting of `~/.golta/config.json`, or saves `policy` there.
+fn auto_install(
+    home: &Path,
+    policy: Option<AutoInstall>,
+    writer: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    let mut config = load_config(home)?;
+    let Some(policy) = policy else {
+        writeln!(
+            writer,
+            "auto_install = \"{}\"",
+            config.auto_install.unwrap_or_default()
+        )?;
+        return Ok(());
+    };
+
+    config.auto_install = Some(policy);
+    save_config(home, &config)?;
+    writeln!(
+        writer,
+        "Saved auto_install = \"{}\" to {}.",
+        policy,
+        config_path(home).display()
+    )?;
+    Ok(())
+}
+
+fn read_project_file(path: &Path) -> Result<ProjectFile, Box<dyn Error>> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Could not read {}: {}", path.display(), e))?;
+    ProjectFile::parse(&content).map_err(|e| format!("{}:{}", path.display(), e).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn accepts_valid_file() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(PROJECT_FILE_NAME);
+        fs::write(
+            &path,
+            r#"{"schema_version": 1, "go": "1.22", "env": {"CGO_ENABLED": "0"}}"#,
+        )
+        .unwrap();
+        let mut out = Vec::new();
+
+        validate(Some(&path), &mut out).unwrap();
+
+        assert!(String::from_utf8(out).unwrap().ends_with("is valid.\n"));
+    }
+
+    #[test]
+    fn reports_position_of_unknown_key() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(PROJECT_FILE_NAME);
+        fs::write(&path, "{\n  \"go\": \"1.22.3\",\n  \"tool\": {}\n}\n").unwrap();
+        let mut out = Vec::new();
+
+        let err = validate(Some(&path), &mut out).unwrap_err();
+
+        assert!(err
+            .to_string()
+            .starts_with(&format!("{}:3:8: unknown field `tool`", path.display())));
+    }
+
+    #[test]
+    fn shows_and_sets_auto_install() {
+        let home = tempdir().unwrap();
+        let mut out = Vec::new();
+
+        auto_install(home.path(), None, &mut out).unwrap();
+        auto_install(home.path(), Some(AutoInstall::Never), &mut out).unwrap();
+        auto_install(home.path(), None, &mut out).unwrap();
+
+        let output = String::from_utf8(out).unwrap();
+        assert!(output.starts_with("auto_install = \"prompt\"\nSaved auto_install = \"never\""));
+        assert!(output.ends_with("auto_install = \"never\"\n"));
+    }
+}
@@ -1,10 +1,21 @@
+use crate::cli::install::install_spec;
 use crate::shared::local_versions::get_installed_versions;
 use crate::DefaultCommand;
+use golta_core::gover::select_version;
+use golta_core::system::is_system_version;
 use std::error::Error;
 use std::fs::{create_dir_all, remove_file, write};
 use std::path::PathBuf;
 
-pub fn run(cmd: DefaultCommand) {
+pub async fn run(cmd: DefaultCommand) {
+    if cmd.install {
+        if let Some((tool, spec)) = cmd.tool.as_deref().and_then(|t| t.split_once('@')) {
+            if let Err(e) = install_spec(tool, spec, &mut std::io::stdout()).await {
+                eprintln!("Error: {}", e);
+                return;
+            }
+        }
+    }
     if let Err(e) = handle_default(cmd, &mut FsDefaultManager::new()) {
         eprintln!("Error: {}", e);
     }
@@ -25,9 +36,33 @@ fn handle_default(
         }
         None => {
             let tool_arg = cmd.tool.expect("clap should ensure tool is present");
-            let (tool, version) = parse_tool_version(&tool_arg)?;
-            manager.set_default(tool, version)?;
-            println!("Set {} default version to {}", tool, version);
+            let (tool, spec) = parse_tool_version(&tool_arg)?;
+            // Check if the version is installed (currently only validates 'go').
+            // A floating spec such as `1.22` or `stable` is stored as written unless --exact.
+            if tool == "go" && !is_system_version(spec) {
+                let installed_versions = manager.installed_versions(tool)?;
+                let resolved = select_version(spec, installed_versions.iter().map(String::as_str))
+                    .ok_or_else(|| {
+                        format!(
+                            "Go version {} is not installed. Install it first with `golta install go@{}`, or pass --install.",
+                            spec, spec
+                        )
+                    })?;
+                let version = if cmd.exact { resolved } else { spec };
+                manager.write_default(tool, version)?;
+                if version == resolved {
+                    println!("Set {} default version to {}", tool, version);
+                } else {
+                    println!(
+                        "Set {} default version to {} (currently {})",
+                        tool, version, resolved
+                    );
+                }
+            } else {
+                // TODO: Add validation for other tools when directory structure supports them
+                manager.write_default(tool, spec)?;
+                println!("Set {} default version to {}", tool, spec);
+            }
         }
     }
     Ok(())
@@ -43,9 +78,11 @@ fn parse_tool_version(input: &str) -> Result<(&str, &str), Box<dyn Error>> {
 /// `DefaultManager` abstracts the operations for managing the default version.
 /// This decouples the dependency on the filesystem and makes testing easier.
 trait DefaultManager {
-    /// Sets the specified version as the default.
-    /// Returns an error if the version is not installed.
-    fn set_default(&mut self, tool: &str, version: &str) -> Result<(), Box<dyn Error>>;
+    /// Lists the installed versions of the tool.
+    fn installed_versions(&self, tool: &str) -> Result<Vec<String>, Box<dyn Error>>;
+
+    /// Stores the version or version spec as the default.
+    fn write_default(&mut self, tool: &str, version: &str) -> Result<(), Box<dyn Error>>;
 
     /// Clears the currently set default version.
     /// Returns `Ok(false)` if no default was set.
@@ -74,20 +111,11 @@ impl FsDefaultManager {
 }
 
 impl DefaultManager for FsDefaultManager {
-    fn set_default(&mut self, tool: &str, version: &str) -> Result<(), Box<dyn Error>> {
-        // Check if the version is installed (currently only validates 'go')
-        if tool == "go" {
-            let installed_versions = get_installed_versions()?;
-            if !installed_versions.iter().any(|v| v == version) {
-                return Err(format!(
-                    "Go version {} is not installed. Please install it first with `golta install go@{}`.",
-                    version, version
-                )
-                .into());
-            }
-        }
-        // TODO: Add validation for other tools when directory structure supports them
+    fn installed_versions(&self, _tool: &str) -> Result<Vec<String>, Box<dyn Error>> {
+        get_installed_versions()
+    }
 
+    fn write_default(&mut self, tool: &str, version: &str) -> Result<(), Box<dyn Error>> {
         create_dir_all(&self.state_dir)?;
         write(self.default_file_path(tool), version)?;
 
@@ -126,10 +154,11 @@ mod tests {
     }
 
     impl DefaultManager for MockManager {
-        fn set_default(&mut self, tool: &str, version: &str) -> Result<(), Box<dyn Error>> {
-            if tool == "go" && !self.installed_versions.contains(version) {
-                return Err("Version not installed".into());
-            }
+        fn installed_versions(&self, _tool: &str) -> Result<Vec<String>, Box<dyn Error>> {
+            Ok(self.installed_versions.iter().cloned().collect())
+        }
+
+        fn write_default(&mut self, _tool: &str, version: &str) -> Result<(), Box<dyn Error>> {
             self.default_version = Some(version.to_string());
             Ok(())
         }
@@ -150,6 +179,8 @@ mod tests {
         let cmd = DefaultCommand {
             command: None,
             tool: Some("go@1.21.0".to_string()),
+            exact: false,
+            install: false,
         };
 
         let result = handle_default(cmd, &mut manager); // この行は変更不要ですが、関数の定義が変わったことで正しく動作します
@@ -163,6 +194,8 @@ mod tests {
         let cmd = DefaultCommand {
             command: None,
             tool: Some("go@1.21.0".to_string()),
+            exact: false,
+            install: false,
         };
 
         let result = handle_default(cmd, &mut manager); // この行は変更不要
@@ -170,12 +203,38 @@ mod tests {
         assert_eq!(manager.default_version, None);
     }
 
+    #[test]
+    fn test_handle_default_floating_spec() {
+        let mut manager = MockManager::new(vec!["1.22.1", "1.22.5", "1.23rc1"], None);
+        let cmd = DefaultCommand {
+            command: None,
+            tool: Some("go@stable".to_string()),
+            exact: false,
+            install: false,
+        };
+
+        handle_default(cmd, &mut manager).unwrap();
+        assert_eq!(manager.default_version, Some("stable".to_string()));
+
+        let cmd = DefaultCommand {
+            command: None,
+            tool: Some("go@1.22".to_string()),
+            exact: true,
+            install: false,
+        };
+
+        handle_default(cmd, &mut manager).unwrap();
+        assert_eq!(manager.default_version, Some("1.22.5".to_string()));
+    }
+
     #[test]
     fn test_handle_default_clear() {
         let mut manager = MockManager::new(vec!["1.21.0"], Some("1.21.0"));
         let cmd = DefaultCommand {
             command: Some(DefaultCommands::Clear),
             tool: None,
+            exact: false,
+            install: false,
         };
 
         let result = handle_default(cmd, &mut manager); // この行は変更不要
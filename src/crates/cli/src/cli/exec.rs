@@ -1,4 +1,9 @@
-use crate::shared::active_version::find_active_go_version;
+use crate::shared::active_version::{find_active_go_version, find_default_tool_version};
+use crate::shared::local_versions::get_installed_tool_versions;
+use golta_core::pinned_version::find_pinned_tool_version;
+use golta_core::resolve::session_override;
+use golta_core::system::{default_shim_path, find_system_program, is_system_version};
+use std::env;
 use std::error::Error;
 use std::path::{Path, PathBuf};
 use std::process::Command;
@@ -22,22 +27,90 @@ fn exec_tool(
     env: &impl GoEnvironment,
     runner: &mut impl GoCommandRunner,
 ) -> Result<i32, Box<dyn Error>> {
+    let version = if is_go_distribution_binary(tool) {
+        resolve_go_version(env)?
+    } else {
+        resolve_tool_version(tool, env)?
+    };
+
+    let binary_path = env.binary_path(tool, &version)?;
+    runner.run(&binary_path, args)
+}
+
+/// Binaries shipped in `$GOROOT/bin`, which follow the active Go version.
+fn is_go_distribution_binary(tool: &str) -> bool {
+    matches!(tool, "go" | "gofmt")
+}
+
+fn resolve_go_version(env: &impl GoEnvironment) -> Result<String, Box<dyn Error>> {
     let version_str = env
         .active_version()?
         .ok_or("No Go version is active. Use `golta pin` or `golta default`.")?;
-    let version = version_str.trim();
-    let version = version.trim_start_matches("go@");
+    let version = version_str.trim().trim_start_matches("go@");
 
     if version.is_empty() {
         return Err("No Go version is active. Use `golta pin` or `golta default`.".into());
     }
+    Ok(version.to_string())
+}
 
-    let binary_path = env.binary_path(tool, version)?;
-    runner.run(&binary_path, args)
+/// Resolves a tool version from the session override, the project pin, then the
+/// global default, then the only installed version.
+fn resolve_tool_version(tool: &str, env: &impl GoEnvironment) -> Result<String, Box<dyn Error>> {
+    let installed = env.installed_tool_versions(tool)?;
+
+    let selected = match env.session_tool_version(tool) {
+        Some(version) => Some(version),
+        None => match env.pinned_tool_version(tool)? {
+            Some(version) => Some(version),
+            None => env.default_tool_version(tool)?,
+        },
+    };
+
+    match selected {
+        Some(version) => {
+            if installed.iter().any(|v| v == &version) {
+                Ok(version)
+            } else {
+                Err(format!(
+                    "{} {} is selected but not installed. {}",
+                    tool,
+                    version,
+                    describe_installed(tool, &installed)
+                )
+                .into())
+            }
+        }
+        None if installed.len() == 1 => Ok(installed[0].clone()),
+        None => Err(format!(
+            "No version of {} is active. {} Use `golta default {}@<version>` or add it to \"tools\" in .golta.json.",
+            tool,
+            describe_installed(tool, &installed),
+            tool
+        )
+        .into()),
+    }
+}
+
+fn describe_installed(tool: &str, installed: &[String]) -> String {
+    if installed.is_empty() {
+        format!(
+            "No versions are installed; run `golta install {}@<version>`.",
+            tool
+        )
+    } else {
+        let mut sorted = installed.to_vec();
+        sorted.sort();
+        format!("Installed versions: {}.", sorted.join(", "))
+    }
 }
 
 trait GoEnvironment {
     fn active_version(&self) -> Result<Option<String>, Box<dyn Error>>;
+    fn session_tool_version(&self, tool: &str) -> Option<String>;
+    fn pinned_tool_version(&self, tool: &str) -> Result<Option<String>, Box<dyn Error>>;
+    fn default_tool_version(&self, tool: &str) -> Result<Option<String>, Box<dyn Error>>;
+    fn installed_tool_versions(&self, tool: &str) -> Result<Vec<String>, Box<dyn Error>>;
     fn binary_path(&self, tool: &str, version: &str) -> Result<PathBuf, Box<dyn Error>>;
 }
 
@@ -48,6 +121,22 @@ impl GoEnvironment for RealGoEnvironment {
         find_active_go_version()
     }
 
+    fn session_tool_version(&self, tool: &str) -> Option<String> {
+        session_override(tool)
+    }
+
+    fn pinned_tool_version(&self, tool: &str) -> Result<Option<String>, Box<dyn Error>> {
+        Ok(find_pinned_tool_version(tool)?.map(|(v, _)| v))
+    }
+
+    fn default_tool_version(&self, tool: &str) -> Result<Option<String>, Box<dyn Error>> {
+        find_default_tool_version(tool)
+    }
+
+    fn installed_tool_versions(&self, tool: &str) -> Result<Vec<String>, Box<dyn Error>> {
+        get_installed_tool_versions(tool)
+    }
+
     fn binary_path(&self, tool: &str, version: &str) -> Result<PathBuf, Box<dyn Error>> {
         let binary_name = if cfg!(windows) {
             format!("{}.exe", tool)
@@ -57,7 +146,15 @@ impl GoEnvironment for RealGoEnvironment {
         let home = home::home_dir().ok_or("Could not find home directory")?;
         let versions_dir = home.join(".golta").join("versions");
 
-        if tool == "go" {
+        if is_go_distribution_binary(tool) && is_system_version(version) {
+            find_system_program(
+                tool,
+                env::var_os("PATH").as_deref(),
+                &home,
+                &[default_shim_path(&home)],
+            )
+            .ok_or_else(|| format!("No system `{}` was found on PATH.", tool).into())
+        } else if is_go_distribution_binary(tool) {
             Ok(versions_dir
                 .join(version.trim_start_matches("go@"))
                 .join("go")
@@ -93,6 +190,10 @@ mod tests {
 
     struct MockEnv {
         active_version: Option<String>,
+        session_tool: Option<String>,
+        pinned_tool: Option<String>,
+        default_tool: Option<String>,
+        installed_tools: Vec<String>,
         go_path: PathBuf,
         requested_tool_version: RefCell<Option<(String, String)>>,
     }
@@ -101,6 +202,10 @@ mod tests {
         fn new(active_version: Option<&str>, go_path: &str) -> Self {
             Self {
                 active_version: active_version.map(ToString::to_string),
+                session_tool: None,
+                pinned_tool: None,
+                default_tool: None,
+                installed_tools: Vec::new(),
                 go_path: PathBuf::from(go_path),
                 requested_tool_version: RefCell::new(None),
             }
@@ -112,6 +217,22 @@ mod tests {
             Ok(self.active_version.clone())
         }
 
+        fn session_tool_version(&self, _tool: &str) -> Option<String> {
+            self.session_tool.clone()
+        }
+
+        fn pinned_tool_version(&self, _tool: &str) -> Result<Option<String>, Box<dyn Error>> {
+            Ok(self.pinned_tool.clone())
+        }
+
+        fn default_tool_version(&self, _tool: &str) -> Result<Option<String>, Box<dyn Error>> {
+            Ok(self.default_tool.clone())
+        }
+
+        fn installed_tool_versions(&self, _tool: &str) -> Result<Vec<String>, Box<dyn Error>> {
+            Ok(self.installed_tools.clone())
+        }
+
         fn binary_path(&self, tool: &str, version: &str) -> Result<PathBuf, Box<dyn Error>> {
             self.requested_tool_version
                 .replace(Some((tool.to_string(), version.to_string())));
@@ -184,4 +305,93 @@ mod tests {
             Some(("gofmt".to_string(), "1.22.1".to_string()))
         );
     }
+
+    #[test]
+    fn resolves_tool_from_pin_before_default() {
+        let mut env = MockEnv::new(Some("1.22.1"), "/tmp/gopls");
+        env.pinned_tool = Some("v0.16.1".to_string());
+        env.default_tool = Some("v0.15.0".to_string());
+        env.installed_tools = vec!["v0.15.0".to_string(), "v0.16.1".to_string()];
+        let mut runner = MockRunner::new(0);
+
+        exec_tool("gopls", &[], &env, &mut runner).unwrap();
+
+        assert_eq!(
+            env.requested_tool_version.borrow().clone(),
+            Some(("gopls".to_string(), "v0.16.1".to_string()))
+        );
+    }
+
+    #[test]
+    fn session_override_beats_tool_pin() {
+        let mut env = MockEnv::new(Some("1.22.1"), "/tmp/gopls");
+        env.session_tool = Some("v0.15.0".to_string());
+        env.pinned_tool = Some("v0.16.1".to_string());
+        env.installed_tools = vec!["v0.15.0".to_string(), "v0.16.1".to_string()];
+        let mut runner = MockRunner::new(0);
+
+        exec_tool("gopls", &[], &env, &mut runner).unwrap();
+
+        assert_eq!(
+            env.requested_tool_version.borrow().clone(),
+            Some(("gopls".to_string(), "v0.15.0".to_string()))
+        );
+    }
+
+    #[test]
+    fn falls_back_to_tool_default() {
+        let mut env = MockEnv::new(Some("1.22.1"), "/tmp/gopls");
+        env.default_tool = Some("v0.15.0".to_string());
+        env.installed_tools = vec!["v0.15.0".to_string(), "v0.16.1".to_string()];
+        let mut runner = MockRunner::new(0);
+
+        exec_tool("gopls", &[], &env, &mut runner).unwrap();
+
+        assert_eq!(
+            env.requested_tool_version.borrow().clone(),
+            Some(("gopls".to_string(), "v0.15.0".to_string()))
+        );
+    }
+
+    #[test]
+    fn uses_single_installed_tool_version() {
+        let mut env = MockEnv::new(None, "/tmp/dlv");
+        env.installed_tools = vec!["v1.22.0".to_string()];
+        let mut runner = MockRunner::new(0);
+
+        exec_tool("dlv", &[], &env, &mut runner).unwrap();
+
+        assert_eq!(
+            env.requested_tool_version.borrow().clone(),
+            Some(("dlv".to_string(), "v1.22.0".to_string()))
+        );
+    }
+
+    #[test]
+    fn lists_installed_versions_when_ambiguous() {
+        let mut env = MockEnv::new(Some("1.22.1"), "/tmp/gopls");
+        env.installed_tools = vec!["v0.16.1".to_string(), "v0.15.0".to_string()];
+        let mut runner = MockRunner::new(0);
+
+        let err = exec_tool("gopls", &[], &env, &mut runner).unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("Installed versions: v0.15.0, v0.16.1."));
+        assert!(runner.last_path.is_none());
+    }
+
+    #[test]
+    fn errors_when_selected_tool_version_missing() {
+        let mut env = MockEnv::new(Some("1.22.1"), "/tmp/gopls");
+        env.pinned_tool = Some("v0.17.0".to_string());
+        env.installed_tools = vec!["v0.16.1".to_string()];
+        let mut runner = MockRunner::new(0);
+
+        let err = exec_tool("gopls", &[], &env, &mut runner).unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("gopls v0.17.0 is selected but not installed"));
+    }
 }
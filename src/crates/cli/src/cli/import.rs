@@ -0,0 +1,554 @@
+use crate::cli::link::{is_valid_name, read_go_root_version};
+use clap::ValueEnum;
+use golta_core::gover::select_version;
+use golta_core::resolve::{default_version_path, installed_go_versions, versions_dir};
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Another Go version manager whose installations can be imported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ImportSource {
+    Goenv,
+    Gvm,
+    Asdf,
+    Mise,
+    G,
+    /// `~/sdk/go1.x.y`, as created by the `golang.org/dl` wrappers.
+    Sdk,
+}
+
+/// How imported toolchains get into `~/.golta/versions`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum ImportMode {
+    #[default]
+    Copy,
+    Move,
+    Hardlink,
+}
+
+pub fn run(from: ImportSource, mode: ImportMode, dry_run: bool) {
+    let home = match home::home_dir() {
+        Some(path) => path,
+        None => {
+            eprintln!("Error: Could not find home directory");
+            return;
+        }
+    };
+    let locations = ManagerLocations::from_env(&home);
+
+    if let Err(e) = import_versions(
+        from,
+        mode,
+        dry_run,
+        &locations,
+        &home,
+        &mut std::io::stdout(),
+    ) {
+        eprintln!("Error: {}", e);
+    }
+}
+
+/// Where each manager keeps its data, honouring their `*_ROOT`/`*_DIR` variables.
+struct ManagerLocations {
+    home: PathBuf,
+    goenv_root: Option<PathBuf>,
+    gvm_root: Option<PathBuf>,
+    asdf_data_dir: Option<PathBuf>,
+    mise_data_dir: Option<PathBuf>,
+    mise_config_dir: Option<PathBuf>,
+    g_home: Option<PathBuf>,
+}
+
+impl ManagerLocations {
+    fn from_env(home: &Path) -> Self {
+        let var = |name: &str| {
+            env::var_os(name)
+                .filter(|v| !v.is_empty())
+                .map(PathBuf::from)
+        };
+        Self {
+            home: home.to_path_buf(),
+            goenv_root: var("GOENV_ROOT"),
+            gvm_root: var("GVM_ROOT"),
+            asdf_data_dir: var("ASDF_DATA_DIR"),
+            mise_data_dir: var("MISE_DATA_DIR")
+                .or_else(|| var("XDG_DATA_HOME").map(|dir| dir.join("mise"))),
+            mise_config_dir: var("MISE_CONFIG_DIR")
+                .or_else(|| var("XDG_CONFIG_HOME").map(|dir| dir.join("mise"))),
+            g_home: var("G_HOME"),
+        }
+    }
+
+    fn root(&self, source: ImportSource) -> PathBuf {
+        let or_home = |dir: &Option<PathBuf>, default: &str| {
+            dir.clone().unwrap_or_else(|| self.home.join(default))
+        };
+        match source {
+            ImportSource::Goenv => or_home(&self.goenv_root, ".goenv"),
+            ImportSource::Gvm => or_home(&self.gvm_root, ".gvm"),
+            ImportSource::Asdf => or_home(&self.asdf_data_dir, ".asdf"),
+            ImportSource::Mise => or_home(&self.mise_data_dir, ".local/share/mise"),
+            ImportSource::G => or_home(&self.g_home, ".g"),
+            ImportSource::Sdk => self.home.join("sdk"),
+        }
+    }
+
+    /// The directory whose entries are the manager's Go installations.
+    fn versions_dir(&self, source: ImportSource) -> PathBuf {
+        let root = self.root(source);
+        match source {
+            ImportSource::Goenv | ImportSource::G => root.join("versions"),
+            ImportSource::Gvm => root.join("gos"),
+            ImportSource::Asdf => root.join("installs").join("golang"),
+            ImportSource::Mise => root.join("installs").join("go"),
+            ImportSource::Sdk => root,
+        }
+    }
+
+    /// Reads the manager's global default Go version, without a `go` prefix.
+    fn global_default(&self, source: ImportSource) -> Option<String> {
+        let root = self.root(source);
+        let version = match source {
+            ImportSource::Goenv => first_line(&root.join("version")),
+            ImportSource::Gvm => {
+                let content = fs::read_to_string(root.join("environments").join("default")).ok()?;
+                content.lines().find_map(|line| {
+                    // e.g. `export gvm_go_name; gvm_go_name="go1.22.3"`
+                    let (_, value) = line.trim().split_once("gvm_go_name=")?;
+                    Some(value.trim_matches(|c| c == '"' || c == '\'').to_string())
+                })
+            }
+            ImportSource::Asdf => {
+                let content = fs::read_to_string(self.home.join(".tool-versions")).ok()?;
+                content.lines().find_map(|line| {
+                    let mut fields = line.split_whitespace();
+                    (fields.next()? == "golang").then(|| fields.next().map(str::to_string))?
+                })
+            }
+            ImportSource::Mise => {
+                let config_dir = self
+                    .mise_config_dir
+                    .clone()
+                    .unwrap_or_else(|| self.home.join(".config").join("mise"));
+                let content = fs::read_to_string(config_dir.join("config.toml")).ok()?;
+                mise_go_version(&content)
+            }
+            ImportSource::G => fs::read_link(root.join("go"))
+                .ok()?
+                .file_name()?
+                .to_str()
+                .map(str::to_string),
+            ImportSource::Sdk => None,
+        }?;
+        let version = version.trim();
+        let version = version.strip_prefix("go").unwrap_or(version);
+        (!version.is_empty() && version != "system").then(|| version.to_string())
+    }
+}
+
+/// A Go installation found in another manager's directory.
+#[derive(Debug, PartialEq)]
+struct Discovered {
+    version: String,
+    go_root: PathBuf,
+}
+
+fn import_versions<W: Write>(
+    source: ImportSource,
+    mode: ImportMode,
+    dry_run: bool,
+    locations: &ManagerLocations,
+    home: &Path,
+    writer: &mut W,
+) -> Result<(), Box<dyn Error>> {
+    let source_dir = locations.versions_dir(source);
+    let found = discover(&source_dir, writer)?;
+    if found.is_empty() {
+        writeln!(
+            writer,
+            "No Go installations found in {}.",
+            source_dir.display()
+        )?;
+    }
+
+    let target_dir = versions_dir(home);
+    let mut available = installed_go_versions(home)?;
+    let verb = match (mode, dry_run) {
+        (ImportMode::Copy, false) => "Copied",
+        (ImportMode::Move, false) => "Moved",
+        (ImportMode::Hardlink, false) => "Hard-linked",
+        (ImportMode::Copy, true) => "Would copy",
+        (ImportMode::Move, true) => "Would move",
+        (ImportMode::Hardlink, true) => "Would hard-link",
+    };
+    for Discovered { version, go_root } in found {
+        let version_dir = target_dir.join(&version);
+        if version_dir.exists() {
+            writeln!(writer, "Go {} is already in golta; skipping.", version)?;
+            continue;
+        }
+        if !dry_run {
+            import_go_root(&go_root, &version_dir, mode)?;
+        }
+        writeln!(writer, "{} Go {} from {}", verb, version, go_root.display())?;
+        available.push(version);
+    }
+
+    import_default(source, locations, home, &available, dry_run, writer)
+}
+
+/// Lists the valid GOROOTs in `source_dir`. An entry is either a GOROOT itself or holds
+/// one in `go/` (asdf). Entries that are not Go installations are reported and skipped.
+fn discover<W: Write>(
+    source_dir: &Path,
+    writer: &mut W,
+) -> Result<Vec<Discovered>, Box<dyn Error>> {
+    if !source_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut found = Vec::new();
+    for entry in fs::read_dir(source_dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        // Skip links such as g's `current` and anything that is not a directory.
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+        let go_root = if path.join("go").join("VERSION").is_file() {
+            path.join("go")
+        } else {
+            path
+        };
+
+        match read_go_root_version(&go_root) {
+            Ok(Some(version)) if is_valid_name(&version) => {
+                found.push(Discovered { version, go_root })
+            }
+            Ok(_) => writeln!(
+                writer,
+                "Skipping {}: not a Go release. Use `golta link` to add it under a name.",
+                go_root.display()
+            )?,
+            Err(e) => writeln!(writer, "Skipping {}", e)?,
+        }
+    }
+    found.sort_by(|a, b| a.version.cmp(&b.version));
+    Ok(found)
+}
+
+fn import_go_root(
+    go_root: &Path,
+    version_dir: &Path,
+    mode: ImportMode,
+) -> Result<(), Box<dyn Error>> {
+    // Build the tree next to its final place and rename it in, so an interrupted import
+    // never leaves a half-copied version behind.
+    let file_name = version_dir
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or("invalid version directory")?;
+    let staging = version_dir.with_file_name(format!(".{}.importing", file_name));
+    if staging.exists() {
+        fs::remove_dir_all(&staging)?;
+    }
+    fs::create_dir_all(&staging)?;
+
+    let result = match mode {
+        ImportMode::Copy => copy_tree(go_root, &staging.join("go"), false),
+        ImportMode::Hardlink => copy_tree(go_root, &staging.join("go"), true).map_err(|e| {
+            format!(
+                "{} (hard links need the same filesystem; try --mode copy)",
+                e
+            )
+            .into()
+        }),
+        ImportMode::Move => move_tree(go_root, &staging.join("go")),
+    };
+    match result {
+        Ok(()) => {
+            fs::rename(&staging, version_dir)?;
+            Ok(())
+        }
+        Err(e) => {
+            let _ = fs::remove_dir_all(&staging);
+            Err(e)
+        }
+    }
+}
+
+fn move_tree(from: &Path, to: &Path) -> Result<(), Box<dyn Error>> {
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    // Different filesystems: copy, then remove the original.
+    copy_tree(from, to, false)?;
+    fs::remove_dir_all(from)?;
+    Ok(())
+}
+
+fn copy_tree(from: &Path, to: &Path, hardlink: bool) -> Result<(), Box<dyn Error>> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        let target = to.join(entry.file_name());
+        if file_type.is_dir() {
+            copy_tree(&entry.path(), &target, hardlink)?;
+        } else if file_type.is_symlink() {
+            copy_symlink(&entry.path(), &target)?;
+        } else if hardlink {
+            fs::hard_link(entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn copy_symlink(from: &Path, to: &Path) -> Result<(), Box<dyn Error>> {
+    std::os::unix::fs::symlink(fs::read_link(from)?, to)?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn copy_symlink(from: &Path, to: &Path) -> Result<(), Box<dyn Error>> {
+    fs::copy(from, to)?;
+    Ok(())
+}
+
+/// Sets golta's default from the manager's global default, unless golta already has one.
+fn import_default<W: Write>(
+    source: ImportSource,
+    locations: &ManagerLocations,
+    home: &Path,
+    available: &[String],
+    dry_run: bool,
+    writer: &mut W,
+) -> Result<(), Box<dyn Error>> {
+    let Some(spec) = locations.global_default(source) else {
+        return Ok(());
+    };
+    let default_file = default_version_path(home, "go");
+    if default_file.exists() {
+        writeln!(
+            writer,
+            "Keeping golta's existing default; the imported default was {}.",
+            spec
+        )?;
+        return Ok(());
+    }
+    // Managers such as mise allow `1.22`; map it onto what golta now has.
+    let Some(version) = select_version(&spec, available.iter().map(String::as_str)) else {
+        writeln!(
+            writer,
+            "The default {} is not available in golta; run `golta install go@{}`.",
+            spec, spec
+        )?;
+        return Ok(());
+    };
+
+    if dry_run {
+        writeln!(writer, "Would set the default Go version to {}", version)?;
+    } else {
+        if let Some(dir) = default_file.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&default_file, version)?;
+        writeln!(writer, "Set the default Go version to {}", version)?;
+    }
+    Ok(())
+}
+
+fn first_line(path: &Path) -> Option<String> {
+    let content = fs::read_to_string(path).ok()?;
+    content
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(str::to_string)
+}
+
+/// Finds `go = "..."` (or the first entry of `go = [...]`) in the `[tools]` table.
+fn mise_go_version(config: &str) -> Option<String> {
+    let mut in_tools = false;
+    for line in config.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_tools = line == "[tools]";
+            continue;
+        }
+        if !in_tools {
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        if key.trim().trim_matches('"') != "go" {
+            continue;
+        }
+        return value
+            .split('"')
+            .nth(1)
+            .filter(|v| !v.is_empty())
+            .map(str::to_string);
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn locations(home: &Path) -> ManagerLocations {
+        ManagerLocations {
+            home: home.to_path_buf(),
+            goenv_root: None,
+            gvm_root: None,
+            asdf_data_dir: None,
+            mise_data_dir: None,
+            mise_config_dir: None,
+            g_home: None,
+        }
+    }
+
+    fn fake_go_root(go_root: &Path, version: &str) {
+        fs::create_dir_all(go_root.join("bin")).unwrap();
+        fs::write(go_root.join("bin").join(exe("go")), "binary").unwrap();
+        fs::write(go_root.join("VERSION"), format!("go{}\n", version)).unwrap();
+        fs::create_dir_all(go_root.join("src")).unwrap();
+        fs::write(go_root.join("src").join("go.mod"), "module std\n").unwrap();
+    }
+
+    fn exe(name: &str) -> String {
+        if cfg!(windows) {
+            format!("{}.exe", name)
+        } else {
+            name.to_string()
+        }
+    }
+
+    #[test]
+    fn copies_goenv_versions_and_default() {
+        let home = tempdir().unwrap();
+        let goenv = home.path().join(".goenv");
+        fake_go_root(&goenv.join("versions").join("1.22.3"), "1.22.3");
+        fake_go_root(&goenv.join("versions").join("1.21.0"), "1.21.0");
+        fs::create_dir_all(goenv.join("versions").join("broken")).unwrap();
+        fs::write(goenv.join("version"), "1.22.3\n").unwrap();
+        let mut out = Vec::new();
+
+        import_versions(
+            ImportSource::Goenv,
+            ImportMode::Copy,
+            false,
+            &locations(home.path()),
+            home.path(),
+            &mut out,
+        )
+        .unwrap();
+
+        let output = String::from_utf8(out).unwrap();
+        let imported = versions_dir(home.path()).join("1.22.3").join("go");
+        assert!(imported.join("bin").join(exe("go")).is_file());
+        assert!(imported.join("src").join("go.mod").is_file());
+        assert!(goenv.join("versions").join("1.22.3").exists());
+        assert!(output.contains("Copied Go 1.21.0"));
+        assert!(output.contains("Skipping"));
+        assert_eq!(
+            fs::read_to_string(default_version_path(home.path(), "go")).unwrap(),
+            "1.22.3"
+        );
+    }
+
+    #[test]
+    fn dry_run_changes_nothing() {
+        let home = tempdir().unwrap();
+        let asdf = home.path().join(".asdf/installs/golang/1.21.6");
+        fake_go_root(&asdf.join("go"), "1.21.6");
+        fs::write(
+            home.path().join(".tool-versions"),
+            "nodejs 20.0.0\ngolang 1.21.6\n",
+        )
+        .unwrap();
+        let mut out = Vec::new();
+
+        import_versions(
+            ImportSource::Asdf,
+            ImportMode::Move,
+            true,
+            &locations(home.path()),
+            home.path(),
+            &mut out,
+        )
+        .unwrap();
+
+        let output = String::from_utf8(out).unwrap();
+        assert!(output.contains("Would move Go 1.21.6"));
+        assert!(output.contains("Would set the default Go version to 1.21.6"));
+        assert!(asdf.join("go").exists());
+        assert!(!versions_dir(home.path()).exists());
+        assert!(!default_version_path(home.path(), "go").exists());
+    }
+
+    #[test]
+    fn moves_sdk_versions_and_skips_existing() {
+        let home = tempdir().unwrap();
+        let sdk = home.path().join("sdk");
+        fake_go_root(&sdk.join("go1.22.3"), "1.22.3");
+        fake_go_root(&sdk.join("go1.20.1"), "1.20.1");
+        fs::create_dir_all(versions_dir(home.path()).join("1.20.1").join("go")).unwrap();
+        let mut out = Vec::new();
+
+        import_versions(
+            ImportSource::Sdk,
+            ImportMode::Move,
+            false,
+            &locations(home.path()),
+            home.path(),
+            &mut out,
+        )
+        .unwrap();
+
+        let output = String::from_utf8(out).unwrap();
+        assert!(!sdk.join("go1.22.3").exists());
+        assert!(versions_dir(home.path())
+            .join("1.22.3/go/VERSION")
+            .is_file());
+        assert!(output.contains("Go 1.20.1 is already in golta; skipping."));
+        assert!(sdk.join("go1.20.1").exists());
+    }
+
+    #[test]
+    fn reads_manager_defaults() {
+        let home = tempdir().unwrap();
+        let locations = locations(home.path());
+        fs::create_dir_all(home.path().join(".gvm/environments")).unwrap();
+        fs::write(
+            home.path().join(".gvm/environments/default"),
+            "export GOROOT; GOROOT=\"/x\"\nexport gvm_go_name; gvm_go_name=\"go1.21.4\"\n",
+        )
+        .unwrap();
+        fs::create_dir_all(home.path().join(".config/mise")).unwrap();
+        fs::write(
+            home.path().join(".config/mise/config.toml"),
+            "[env]\ngo = \"nope\"\n\n[tools]\nnode = \"20\"\ngo = \"1.22\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            locations.global_default(ImportSource::Gvm).as_deref(),
+            Some("1.21.4")
+        );
+        assert_eq!(
+            locations.global_default(ImportSource::Mise).as_deref(),
+            Some("1.22")
+        );
+        assert_eq!(locations.global_default(ImportSource::Goenv), None);
+    }
+}
@@ -1,18 +1,17 @@
-use crate::shared::os_info::get_os_arch_and_format;
-use crate::shared::versions::{fetch_remote_versions, GoVersionInfo};
-use futures_util::StreamExt;
-use indicatif::{ProgressBar, ProgressStyle};
+use crate::shared::active_version::find_active_go_version;
+use crate::shared::tool_builds::{write_build_info, ToolBuildInfo};
+use crate::shared::tool_compat::{fetch_go_requirement, newest_compatible_version};
+use crate::shared::versions::fetch_tool_versions;
+use golta_core::go_mod::{find_go_version_file, parse_go_mod, GoWork};
+use golta_core::install::{build_install_dir, install_go_version};
+use golta_core::system::{default_shim_path, find_system_program, is_system_version};
 use serde::Deserialize;
+use std::env;
 use std::error::Error;
 use std::fs;
-#[cfg(windows)]
-use std::fs::File;
-use std::future::Future;
-use std::io::{self, Cursor, Write};
+use std::io::{self, Write};
 use std::path::{Path, PathBuf};
 use std::process::Command;
-#[cfg(windows)]
-use zip::ZipArchive;
 
 pub async fn run(tool_arg: String) {
     let home = match home::home_dir() {
@@ -41,74 +40,31 @@ pub async fn run(tool_arg: String) {
         return;
     }
 
-    if let Err(e) = install_go(
-        &version_spec,
-        &home,
-        fetch_remote_versions,
-        download_with_progress,
-        &mut stdout,
-    )
-    .await
-    {
+    if let Err(e) = install_go_version(&version_spec, &home, &mut stdout).await {
         eprintln!("Error: {}", e);
         std::process::exit(1);
     }
 }
 
-async fn install_go<W, FetchVersions, FetchVersionsFut, DownloadBytes, DownloadBytesFut>(
-    version_spec: &str,
-    home: &Path,
-    fetch_versions: FetchVersions,
-    download_bytes: DownloadBytes,
-    writer: &mut W,
-) -> Result<(), Box<dyn Error>>
-where
-    W: Write,
-    FetchVersions: Fn() -> FetchVersionsFut,
-    FetchVersionsFut: Future<Output = Result<Vec<GoVersionInfo>, Box<dyn Error>>>,
-    DownloadBytes: Fn(String) -> DownloadBytesFut,
-    DownloadBytesFut: Future<Output = Result<Vec<u8>, Box<dyn Error>>>,
-{
-    let version = resolve_go_version(version_spec, fetch_versions, writer).await?;
-
-    writeln!(writer, "Installing Go version {}", version)?;
-
-    let install_dir = build_install_dir(home, &version);
-
-    if install_dir.exists() {
-        writeln!(writer, "Go {} is already installed.", version)?;
-        return Ok(());
-    }
-
-    let (os_arch, archive_format) = get_os_arch_and_format();
-    let url = build_download_url(&version, os_arch, archive_format);
-    writeln!(writer, "Downloading {} ...", url)?;
-
-    let bytes = download_bytes(url).await?;
-
-    fs::create_dir_all(&install_dir)?;
-
-    writeln!(writer, "Extracting...")?;
-    let extract_pb = ProgressBar::new_spinner();
-    extract_pb.set_style(ProgressStyle::with_template(
-        "{spinner:.green} extracting {msg}",
-    )?);
-    #[cfg(target_os = "windows")]
-    {
-        extract_zip(&bytes, &install_dir, &extract_pb)?;
-    }
-
-    #[cfg(not(target_os = "windows"))]
-    {
-        extract_tar_gz(&bytes, &install_dir, &extract_pb)?;
+/// Installs `tool` at `version` for `pin --install` and `default --install`, resolving
+/// specs such as `1.22` or `stable` the same way `golta install` does. `system` is not
+/// golta's to install and is left alone.
+pub(crate) async fn install_spec(
+    tool: &str,
+    version: &str,
+    writer: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    let home = home::home_dir().ok_or("Could not find home directory")?;
+    if tool != "go" {
+        install_go_tool(tool, version, &home, writer).await
+    } else if is_system_version(version) {
+        Ok(())
+    } else {
+        install_go_version(version, &home, writer).await
     }
-    extract_pb.finish_with_message("Extracted");
-
-    writeln!(writer, "Go {} installed to {:?}", version, install_dir)?;
-    Ok(())
 }
 
-async fn install_go_tool(
+pub(crate) async fn install_go_tool(
     tool: &str,
     version: &str,
     home: &Path,
@@ -119,6 +75,18 @@ async fn install_go_tool(
         tool
     ))?;
 
+    install_go_package(tool, package_path, module_path, version, home, writer).await
+}
+
+/// Builds `package_path` at `version` with `go install` into `~/.golta/versions/<tool>/<version>/bin`.
+pub(crate) async fn install_go_package(
+    tool: &str,
+    package_path: &str,
+    module_path: &str,
+    version: &str,
+    home: &Path,
+    writer: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
     let resolved_version = if version == "latest" {
         resolve_latest_tool_version(module_path, writer).await?
     } else {
@@ -141,18 +109,32 @@ async fn install_go_tool(
         return Ok(());
     }
 
-    writeln!(writer, "Installing {}@{}...", tool, resolved_version)?;
+    let active_go = find_active_go_version()
+        .ok()
+        .flatten()
+        .map(|v| v.trim_start_matches("go@").to_string());
+    let build_go = BuildGo::for_version(home, active_go.as_deref());
+    match compat_go_version(active_go.as_deref(), &build_go) {
+        Some(active) => {
+            ensure_tool_compatible(tool, module_path, &resolved_version, active, writer).await?
+        }
+        None => writeln!(
+            writer,
+            "Warning: no active Go version; skipping the compatibility check for {}.",
+            tool
+        )?,
+    }
 
-    let status = Command::new("go")
-        .arg("install")
-        .arg(format!("{}@{}", package_path, resolved_version))
-        .env("GOBIN", &bin_dir)
-        .status()
-        .map_err(|e| format!("Failed to execute 'go install': {}. Make sure 'go' is in your PATH or installed via golta.", e))?;
+    writeln!(writer, "Installing {}@{}...", tool, resolved_version)?;
 
-    if !status.success() {
-        return Err(format!("Failed to install {}@{}", tool, resolved_version).into());
-    }
+    build_tool(tool, package_path, &resolved_version, &bin_dir, &build_go)?;
+    write_build_info(
+        &install_dir,
+        &ToolBuildInfo {
+            package: package_path.to_string(),
+            go: build_go.version.clone(),
+        },
+    )?;
 
     writeln!(
         writer,
@@ -163,6 +145,159 @@ async fn install_go_tool(
     Ok(())
 }
 
+/// The Go version to check a tool's go.mod against: the active version, or for `system`
+/// the version reported by the system Go that builds it.
+fn compat_go_version<'a>(active_go: Option<&'a str>, build_go: &'a BuildGo) -> Option<&'a str> {
+    match active_go {
+        Some(active) if is_system_version(active) => build_go.version.as_deref(),
+        active => active,
+    }
+}
+
+/// Refuses to build a tool whose go.mod requires a newer Go than the active one,
+/// suggesting the newest release that still builds.
+async fn ensure_tool_compatible(
+    tool: &str,
+    module_path: &str,
+    version: &str,
+    active_go: &str,
+    writer: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    let requirement = match fetch_go_requirement(module_path, version).await {
+        Ok(requirement) => requirement,
+        Err(e) => {
+            writeln!(
+                writer,
+                "Warning: could not read the go.mod of {}@{} ({}); skipping the compatibility check.",
+                module_path, version, e
+            )?;
+            return Ok(());
+        }
+    };
+
+    if requirement.is_satisfied_by(active_go) {
+        return Ok(());
+    }
+
+    let required = requirement.go.unwrap_or_default();
+    let available = fetch_tool_versions(module_path.to_string())
+        .await
+        .map(|versions| versions.into_iter().map(|v| v.version).collect::<Vec<_>>())
+        .unwrap_or_default();
+    let suggestion = match newest_compatible_version(module_path, &available, active_go).await {
+        Some(compatible) => format!(
+            " The newest compatible version is {}; install it with `golta install {}@{}`.",
+            compatible, tool, compatible
+        ),
+        None => format!(
+            " Install Go {} or newer with `golta install go@{}`.",
+            required, required
+        ),
+    };
+
+    Err(format!(
+        "{} {} requires Go {} or newer, but the active Go version is {}.{}",
+        tool, version, required, active_go, suggestion
+    )
+    .into())
+}
+
+/// The Go toolchain used to build tools with `go install`.
+#[derive(Debug)]
+pub(crate) struct BuildGo {
+    go_path: PathBuf,
+    go_root: Option<PathBuf>,
+    pub(crate) version: Option<String>,
+}
+
+impl BuildGo {
+    /// Uses the golta-managed toolchain for `version`, if it is installed.
+    pub(crate) fn installed(home: &Path, version: &str) -> Option<Self> {
+        let go_executable_name = if cfg!(windows) { "go.exe" } else { "go" };
+        let go_root = build_install_dir(home, version).join("go");
+        let go_path = go_root.join("bin").join(go_executable_name);
+        go_path.exists().then(|| Self {
+            go_path,
+            go_root: Some(go_root),
+            version: Some(version.to_string()),
+        })
+    }
+
+    /// Uses the system Go found on PATH, skipping golta's own shim.
+    pub(crate) fn system(home: &Path) -> Option<Self> {
+        let go_path = find_system_program(
+            "go",
+            env::var_os("PATH").as_deref(),
+            home,
+            &[default_shim_path(home)],
+        )?;
+        Some(Self::from_path(go_path))
+    }
+
+    /// Uses the active golta-managed toolchain (or the system Go for `system`), falling
+    /// back to `go` on PATH.
+    fn for_version(home: &Path, version: Option<&str>) -> Self {
+        let build_go = match version {
+            Some(version) if is_system_version(version) => Self::system(home),
+            Some(version) => Self::installed(home, version),
+            None => None,
+        };
+        build_go.unwrap_or_else(|| Self::from_path(PathBuf::from("go")))
+    }
+
+    /// Uses `go_path` as found, asking it for its version.
+    fn from_path(go_path: PathBuf) -> Self {
+        let version = Command::new(&go_path)
+            .args(["env", "GOVERSION"])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| {
+                String::from_utf8_lossy(&output.stdout)
+                    .trim()
+                    .trim_start_matches("go")
+                    .to_string()
+            })
+            .filter(|v| !v.is_empty());
+        Self {
+            go_path,
+            go_root: None,
+            version,
+        }
+    }
+
+    fn command(&self) -> Command {
+        let mut command = Command::new(&self.go_path);
+        if let Some(go_root) = &self.go_root {
+            // Build with exactly this toolchain rather than letting go switch on its own.
+            command.env("GOROOT", go_root).env("GOTOOLCHAIN", "local");
+        }
+        command
+    }
+}
+
+/// Runs `go install package@version` with `GOBIN` pointing at `bin_dir`.
+pub(crate) fn build_tool(
+    tool: &str,
+    package_path: &str,
+    version: &str,
+    bin_dir: &Path,
+    build_go: &BuildGo,
+) -> Result<(), Box<dyn Error>> {
+    let status = build_go
+        .command()
+        .arg("install")
+        .arg(format!("{}@{}", package_path, version))
+        .env("GOBIN", bin_dir)
+        .status()
+        .map_err(|e| format!("Failed to execute 'go install': {}. Make sure 'go' is in your PATH or installed via golta.", e))?;
+
+    if !status.success() {
+        return Err(format!("Failed to install {}@{}", tool, version).into());
+    }
+    Ok(())
+}
+
 fn parse_tool_and_version(input: &str) -> Result<(String, String), Box<dyn Error>> {
     if input == "go" {
         if let Some(v) = read_go_mod_version() {
@@ -217,185 +352,12 @@ async fn resolve_latest_tool_version(
     Ok(proxy_version.version)
 }
 
-async fn resolve_go_version<F, Fut>(
-    spec: &str,
-    fetch_versions: F,
-    writer: &mut impl Write,
-) -> Result<String, Box<dyn Error>>
-where
-    F: Fn() -> Fut,
-    Fut: Future<Output = Result<Vec<GoVersionInfo>, Box<dyn Error>>>,
-{
-    writeln!(writer, "Finding matching Go version for \"{}\"...", spec)?;
-    let remote_versions: Vec<GoVersionInfo> = fetch_versions().await?;
-
-    resolve_go_version_from_list(spec, &remote_versions, writer)
-}
-
-fn resolve_go_version_from_list(
-    spec: &str,
-    versions: &[GoVersionInfo],
-    writer: &mut impl Write,
-) -> Result<String, Box<dyn Error>> {
-    if spec == "latest" {
-        let latest_stable = versions
-            .iter()
-            .find(|v| v.stable)
-            .ok_or("Could not find a stable Go version.")?;
-        return Ok(latest_stable.version.trim_start_matches("go").to_string());
-    }
-
-    let found_version = versions
-        .iter()
-        .find(|v| v.version.trim_start_matches("go") == spec);
-
-    match found_version {
-        Some(info) => {
-            let version_str = info.version.trim_start_matches("go");
-            writeln!(writer, "Found matching version: {}", version_str).ok();
-            Ok(version_str.to_string())
-        }
-        None => Err(format!(
-            "Go version '{}' not found. Please specify an exact version from `golta list-remote`.",
-            spec
-        )
-        .into()),
-    }
-}
-
-fn build_install_dir(home: &Path, version: &str) -> PathBuf {
-    home.join(".golta").join("versions").join(version)
-}
-
-fn build_download_url(version: &str, os_arch: &str, archive_format: &str) -> String {
-    format!(
-        "https://golang.org/dl/go{}.{}.{}",
-        version, os_arch, archive_format
-    )
-}
-
-async fn download_with_progress(url: String) -> Result<Vec<u8>, Box<dyn Error>> {
-    let response = reqwest::get(&url).await?.error_for_status()?;
-
-    let total_size = response
-        .content_length()
-        .ok_or_else(|| format!("Failed to get content length from {}", &url))?;
-
-    let pb = ProgressBar::new(total_size);
-    pb.set_style(ProgressStyle::default_bar()
-        .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {bytes}/{total_bytes} ({eta})")?
-        .progress_chars("#>-"));
-
-    let capacity = total_size
-        .try_into()
-        .map_err(|_| "File size is too large to fit in memory on this system.".to_string())?;
-    let mut downloaded_bytes = Vec::with_capacity(capacity);
-    let mut stream = response.bytes_stream();
-
-    while let Some(chunk) = stream.next().await {
-        let chunk = chunk?;
-        downloaded_bytes.extend_from_slice(&chunk);
-        pb.inc(chunk.len() as u64);
-    }
-
-    pb.finish_with_message("Downloaded");
-
-    Ok(downloaded_bytes)
-}
-
-#[cfg(not(target_os = "windows"))]
-fn extract_tar_gz(
-    bytes: &[u8],
-    install_dir: &Path,
-    pb: &ProgressBar,
-) -> Result<(), Box<dyn Error>> {
-    use flate2::read::GzDecoder;
-    use tar::Archive;
-    let tar_gz_cursor = Cursor::new(bytes);
-    let tar = GzDecoder::new(tar_gz_cursor);
-    let mut archive = Archive::new(tar);
-    let temp_extract_dir = install_dir.join("go_temp");
-
-    if !temp_extract_dir.exists() {
-        fs::create_dir_all(&temp_extract_dir)?;
-    }
-
-    archive.set_preserve_permissions(false);
-    archive.set_preserve_mtime(false);
-
-    for entry in archive.entries()? {
-        let mut entry = entry?;
-        entry.unpack_in(&temp_extract_dir)?;
-        pb.set_message("...");
-        pb.tick();
-    }
-
-    let source_path = temp_extract_dir.join("go");
-    let destination_path = install_dir.join("go");
-
-    if !source_path.exists() {
-        return Err(std::io::Error::new(
-            std::io::ErrorKind::NotFound,
-            "Source directory not found",
-        )
-        .into());
-    }
-    if destination_path.exists() {
-        return Err(std::io::Error::new(
-            std::io::ErrorKind::AlreadyExists,
-            "Destination directory already exists",
-        )
-        .into());
-    }
-
-    fs::rename(temp_extract_dir.join("go"), install_dir.join("go"))?;
-    Ok(())
-}
-
-/// Extracts a byte slice as a zip archive
-#[cfg(windows)]
-fn extract_zip(bytes: &[u8], dest: &Path, pb: &ProgressBar) -> std::io::Result<()> {
-    let cursor = Cursor::new(bytes);
-    let mut zip = ZipArchive::new(cursor)?;
-    let entries = zip.len();
-    pb.set_length(entries as u64);
-    pb.set_style(
-        ProgressStyle::with_template("{spinner:.green} [{pos}/{len}] extracting {msg}")
-            .unwrap_or_else(|_| ProgressStyle::default_spinner()),
-    );
-
-    for i in 0..entries {
-        let mut file = zip.by_index(i)?;
-        let name = file.name().to_string();
-
-        // Extract into the `go/` directory
-        let outpath = dest.join(file.name());
-        if file.name().is_empty() {
-            continue;
-        };
-
-        if file.is_dir() {
-            fs::create_dir_all(&outpath)?;
-        } else {
-            if let Some(p) = outpath.parent() {
-                fs::create_dir_all(p)?;
-            }
-            std::io::copy(&mut file, &mut File::create(outpath)?)?;
-        }
-        pb.set_message(name);
-        pb.inc(1);
-    }
-
-    Ok(())
-}
-
+/// Reads the version requested by the enclosing go.work, or else the nearest go.mod
+/// (`toolchain` when newer than `go`).
 fn read_go_mod_version() -> Option<String> {
-    let path = std::env::current_dir().ok()?.join("go.mod");
+    let path = find_go_version_file(&std::env::current_dir().ok()?, &GoWork::from_env())?;
     let content = fs::read_to_string(path).ok()?;
-    content
-        .lines()
-        .find(|line| line.starts_with("go "))
-        .map(|line| line.trim_start_matches("go ").trim().to_string())
+    parse_go_mod(&content).selected_version()
 }
 
 #[cfg(test)]
@@ -427,99 +389,15 @@ mod tests {
     }
 
     #[test]
-    fn builds_download_url_with_os_and_format() {
-        let url = build_download_url("1.22.3", "linux-amd64", "tar.gz");
-        assert_eq!(url, "https://golang.org/dl/go1.22.3.linux-amd64.tar.gz");
-    }
-
-    #[test]
-    fn resolves_latest_version() {
-        let versions = vec![
-            GoVersionInfo {
-                version: "go1.21.9".into(),
-                stable: false,
-            },
-            GoVersionInfo {
-                version: "go1.22.3".into(),
-                stable: true,
-            },
-        ];
-
-        let mut buffer = Vec::new();
-        let resolved = resolve_go_version_from_list("latest", &versions, &mut buffer).unwrap();
-        assert_eq!(resolved, "1.22.3");
-    }
-
-    #[test]
-    fn resolves_exact_version() {
-        let versions = vec![GoVersionInfo {
-            version: "go1.20.1".into(),
-            stable: true,
-        }];
-
-        let mut buffer = Vec::new();
-        let resolved = resolve_go_version_from_list("1.20.1", &versions, &mut buffer).unwrap();
-        assert_eq!(resolved, "1.20.1");
-    }
-
-    #[test]
-    fn errors_when_version_not_found() {
-        let versions = vec![GoVersionInfo {
-            version: "go1.20.1".into(),
-            stable: true,
-        }];
-
-        let mut buffer = Vec::new();
-        let err = resolve_go_version_from_list("1.99.0", &versions, &mut buffer).unwrap_err();
-        assert!(err.to_string().contains("not found"));
-    }
-
-    #[test]
-    fn skips_install_if_already_exists() {
-        let home = temp_home();
-        let version = "1.21.0";
-        let install_dir = home.join(".golta").join("versions").join(version);
-        fs::create_dir_all(&install_dir).unwrap();
-
-        let mut buffer = Vec::new();
-
-        let fetcher = || async {
-            Ok(vec![GoVersionInfo {
-                version: format!("go{}", version),
-                stable: true,
-            }])
+    fn system_go_is_checked_at_its_own_version() {
+        let build_go = BuildGo {
+            go_path: PathBuf::from("/usr/local/go/bin/go"),
+            go_root: None,
+            version: Some("1.22.5".to_string()),
         };
 
-        let downloader = |_| async {
-            panic!("Downloader should not be called");
-            #[allow(unreachable_code)]
-            Ok(vec![])
-        };
-
-        let rt = tokio::runtime::Runtime::new().unwrap();
-        rt.block_on(async {
-            install_go(version, &home, fetcher, downloader, &mut buffer)
-                .await
-                .unwrap();
-        });
-
-        let output = String::from_utf8(buffer).unwrap();
-        assert!(output.contains(&format!("Go {} is already installed.", version)));
-
-        fs::remove_dir_all(home).unwrap();
-    }
-
-    fn temp_home() -> PathBuf {
-        let mut path = std::env::temp_dir();
-        let unique = format!(
-            "golta_install_test_{}_{}",
-            std::process::id(),
-            std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_nanos()
-        );
-        path.push(unique);
-        path
+        assert_eq!(compat_go_version(Some("system"), &build_go), Some("1.22.5"));
+        assert_eq!(compat_go_version(Some("1.21.0"), &build_go), Some("1.21.0"));
+        assert_eq!(compat_go_version(None, &build_go), None);
     }
 }
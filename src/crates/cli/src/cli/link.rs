@@ -0,0 +1,204 @@
+use golta_core::gover::GoVersion;
+use golta_core::resolve::versions_dir;
+use golta_core::system::is_system_version;
+use std::error::Error;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+pub fn run(tool: String, go_root: PathBuf) {
+    let home = match home::home_dir() {
+        Some(path) => path,
+        None => {
+            eprintln!("Error: Could not find home directory");
+            return;
+        }
+    };
+
+    if let Err(e) = link_go(&tool, &go_root, &home, &mut std::io::stdout()) {
+        eprintln!("Error: {}", e);
+    }
+}
+
+/// Registers an existing GOROOT as `~/.golta/versions/<name>/go`, a symlink to it, so
+/// `pin`, `default`, `run` and the shim treat it like an installed version.
+fn link_go<W: Write>(
+    tool: &str,
+    go_root: &Path,
+    home: &Path,
+    writer: &mut W,
+) -> Result<(), Box<dyn Error>> {
+    let name = match tool.split_once('@') {
+        Some(("go", name)) if !name.is_empty() => Some(name),
+        None if tool == "go" => None,
+        _ => return Err("Invalid format. Use `golta link go@<name> <path-to-goroot>`.".into()),
+    };
+
+    let go_root = fs::canonicalize(go_root)
+        .map_err(|e| format!("Could not read {}: {}", go_root.display(), e))?;
+    let version = read_go_root_version(&go_root)?;
+    let name = match (name, &version) {
+        (Some(name), _) => name.to_string(),
+        (None, Some(version)) => version.clone(),
+        (None, None) => {
+            return Err(format!(
+                "Could not tell the Go version of {}. Name it with `golta link go@<name> {}`.",
+                go_root.display(),
+                go_root.display()
+            )
+            .into())
+        }
+    };
+    if !is_valid_name(&name) {
+        return Err(format!("Invalid name '{}'.", name).into());
+    }
+
+    let versions_dir = versions_dir(home);
+    if let Ok(managed) = fs::canonicalize(&versions_dir) {
+        if go_root.starts_with(managed) {
+            return Err(format!("{} is already managed by golta.", go_root.display()).into());
+        }
+    }
+    let version_dir = versions_dir.join(&name);
+    if version_dir.exists() {
+        return Err(format!(
+            "go@{} already exists. Choose another name or uninstall it first.",
+            name
+        )
+        .into());
+    }
+
+    fs::create_dir_all(&version_dir)?;
+    if let Err(e) = create_dir_link(&go_root, &version_dir.join("go")) {
+        let _ = fs::remove_dir(&version_dir);
+        return Err(e);
+    }
+
+    match version {
+        Some(version) if version != name => writeln!(
+            writer,
+            "Linked Go {} at {} as go@{}.",
+            version,
+            go_root.display(),
+            name
+        )?,
+        _ => writeln!(writer, "Linked {} as go@{}.", go_root.display(), name)?,
+    }
+    Ok(())
+}
+
+/// Checks that `go_root` contains `bin/go` and returns the version from its `VERSION` file,
+/// or `None` for builds whose `VERSION` is not a release (e.g. `devel`).
+pub fn read_go_root_version(go_root: &Path) -> Result<Option<String>, Box<dyn Error>> {
+    let go_name = if cfg!(windows) { "go.exe" } else { "go" };
+    if !go_root.join("bin").join(go_name).is_file() {
+        return Err(format!(
+            "{} is not a Go installation: bin/{} is missing.",
+            go_root.display(),
+            go_name
+        )
+        .into());
+    }
+
+    let content = fs::read_to_string(go_root.join("VERSION")).map_err(|_| {
+        format!(
+            "{} is not a Go installation: VERSION is missing.",
+            go_root.display()
+        )
+    })?;
+    let first_line = content.lines().next().unwrap_or("").trim();
+    Ok(first_line
+        .strip_prefix("go")
+        .filter(|version| GoVersion::parse(version).is_some())
+        .map(str::to_string))
+}
+
+/// Returns the external GOROOT a version directory links to, if it was added by `golta link`.
+pub fn linked_go_root(version_dir: &Path) -> Option<PathBuf> {
+    fs::read_link(version_dir.join("go")).ok()
+}
+
+/// Whether `name` can be used as a version directory under `~/.golta/versions`.
+pub fn is_valid_name(name: &str) -> bool {
+    !is_system_version(name)
+        && name != "."
+        && name != ".."
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_' | '+'))
+}
+
+#[cfg(unix)]
+fn create_dir_link(target: &Path, link: &Path) -> Result<(), Box<dyn Error>> {
+    std::os::unix::fs::symlink(target, link)?;
+    Ok(())
+}
+
+#[cfg(windows)]
+fn create_dir_link(target: &Path, link: &Path) -> Result<(), Box<dyn Error>> {
+    std::os::windows::fs::symlink_dir(target, link).map_err(|e| {
+        format!(
+            "Could not create a symlink ({}). Enable Developer Mode or run as administrator.",
+            e
+        )
+        .into()
+    })
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn fake_go_root(dir: &Path, version_file: &str) -> PathBuf {
+        let go_root = dir.join("sdk").join("go");
+        fs::create_dir_all(go_root.join("bin")).unwrap();
+        fs::write(go_root.join("bin").join("go"), "").unwrap();
+        fs::write(go_root.join("VERSION"), version_file).unwrap();
+        go_root
+    }
+
+    #[test]
+    fn links_go_root_under_its_version() {
+        let home = tempdir().unwrap();
+        let go_root = fake_go_root(home.path(), "go1.22.3\ntime 2024-05-01T19:59:58Z\n");
+        let mut out = Vec::new();
+
+        link_go("go", &go_root, home.path(), &mut out).unwrap();
+
+        let version_dir = versions_dir(home.path()).join("1.22.3");
+        assert_eq!(
+            linked_go_root(&version_dir),
+            Some(fs::canonicalize(&go_root).unwrap())
+        );
+        assert!(version_dir.join("go").join("bin").join("go").is_file());
+        assert!(String::from_utf8(out).unwrap().contains("as go@1.22.3"));
+    }
+
+    #[test]
+    fn custom_builds_need_a_name() {
+        let home = tempdir().unwrap();
+        let go_root = fake_go_root(home.path(), "devel go1.23-abcdef\n");
+        let mut out = Vec::new();
+
+        assert!(link_go("go", &go_root, home.path(), &mut out).is_err());
+        link_go("go@tip", &go_root, home.path(), &mut out).unwrap();
+
+        assert!(linked_go_root(&versions_dir(home.path()).join("tip")).is_some());
+        // The same name cannot be linked twice.
+        assert!(link_go("go@tip", &go_root, home.path(), &mut out).is_err());
+    }
+
+    #[test]
+    fn rejects_trees_without_go_binary() {
+        let home = tempdir().unwrap();
+        let go_root = home.path().join("empty");
+        fs::create_dir_all(&go_root).unwrap();
+        let mut out = Vec::new();
+
+        let err = link_go("go@x", &go_root, home.path(), &mut out).unwrap_err();
+
+        assert!(err.to_string().contains("bin/go is missing"));
+        assert!(!versions_dir(home.path()).join("x").exists());
+    }
+}
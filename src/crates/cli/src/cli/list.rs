@@ -1,9 +1,11 @@
-use crate::shared::local_versions::get_installed_versions;
-use crate::shared::pinned_version::find_pinned_go_version;
+use crate::cli::link::linked_go_root;
+use crate::shared::local_versions::{get_installed_tool_versions, get_installed_versions};
+use golta_core::gover::select_version;
+use golta_core::pinned_version::find_pinned_go_version;
+use golta_core::resolve::versions_dir;
 use regex::Regex;
 use semver::Version;
 use std::error::Error;
-use std::fs;
 use std::io::Write;
 use std::path::PathBuf;
 
@@ -29,11 +31,25 @@ fn list_versions(
     } else {
         None
     };
-    let active_version = pinned_version.clone().or_else(|| default_version.clone());
 
     writeln!(out, "Installed {} versions:", tool)?;
 
     let installed_strings = ctx.installed_versions(tool)?;
+    // Floating specs such as `1.22` or `stable` select an installed Go version.
+    let resolve = |spec: &Option<String>| -> Option<String> {
+        let spec = spec.as_deref()?;
+        if tool != "go" {
+            return Some(spec.to_string());
+        }
+        select_version(spec, installed_strings.iter().map(String::as_str)).map(str::to_string)
+    };
+    let resolved_default = resolve(&default_version);
+    let resolved_pinned = resolve(&pinned_version);
+    let active_version = if pinned_version.is_some() {
+        resolved_pinned.clone()
+    } else {
+        resolved_default.clone()
+    };
     let mut sortable_versions: Vec<(Option<Version>, String)> = installed_strings
         .iter()
         .map(|s| {
@@ -58,14 +74,25 @@ fn list_versions(
         let version = original_version_str;
         let mut tags = Vec::new();
         let is_active = active_version.as_deref() == Some(version.as_str());
-        let is_default = default_version.as_deref() == Some(&version);
-        let is_pinned = pinned_version.as_deref() == Some(&version);
-
-        if is_default {
-            tags.push("default");
+        for (label, spec, resolved) in [
+            ("default", &default_version, &resolved_default),
+            ("pinned", &pinned_version, &resolved_pinned),
+        ] {
+            match spec {
+                Some(spec) if resolved.as_deref() == Some(&version) => {
+                    if *spec == version {
+                        tags.push(label.to_string());
+                    } else {
+                        tags.push(format!("{}: {}", label, spec));
+                    }
+                }
+                _ => {}
+            }
         }
-        if is_pinned {
-            tags.push("pinned");
+        if tool == "go" {
+            if let Some(go_root) = ctx.linked_go_root(&version) {
+                tags.push(format!("linked: {}", go_root.display()));
+            }
         }
 
         let prefix = if is_active { "*" } else { " " };
@@ -86,6 +113,7 @@ trait ListContext {
     fn read_default_version(&self, tool: &str) -> Option<String>;
     fn pinned_go_version(&self) -> Result<Option<String>, Box<dyn Error>>;
     fn installed_versions(&self, tool: &str) -> Result<Vec<String>, Box<dyn Error>>;
+    fn linked_go_root(&self, version: &str) -> Option<PathBuf>;
 }
 
 struct FsListContext;
@@ -116,24 +144,13 @@ impl ListContext for FsListContext {
         if tool == "go" {
             get_installed_versions()
         } else {
-            let home = self.home_dir().ok_or("Could not find home directory")?;
-            let tool_dir = home.join(".golta").join("versions").join(tool);
-            if !tool_dir.exists() {
-                return Ok(Vec::new());
-            }
-
-            let mut versions = Vec::new();
-            for entry in fs::read_dir(tool_dir)? {
-                let entry = entry?;
-                if entry.file_type()?.is_dir() {
-                    if let Ok(name) = entry.file_name().into_string() {
-                        versions.push(name);
-                    }
-                }
-            }
-            Ok(versions)
+            get_installed_tool_versions(tool)
         }
     }
+
+    fn linked_go_root(&self, version: &str) -> Option<PathBuf> {
+        linked_go_root(&versions_dir(&self.home_dir()?).join(version))
+    }
 }
 
 // Helper function to normalize version strings to a semver-compatible format
@@ -164,6 +181,7 @@ mod tests {
         default: Option<String>,
         pinned: Option<String>,
         installed: Vec<String>,
+        linked: Option<(String, PathBuf)>,
     }
 
     impl Default for MockCtx {
@@ -173,6 +191,7 @@ mod tests {
                 default: None,
                 pinned: None,
                 installed: Vec::new(),
+                linked: None,
             }
         }
     }
@@ -193,6 +212,13 @@ mod tests {
         fn installed_versions(&self, _tool: &str) -> Result<Vec<String>, Box<dyn Error>> {
             Ok(self.installed.clone())
         }
+
+        fn linked_go_root(&self, version: &str) -> Option<PathBuf> {
+            self.linked
+                .as_ref()
+                .filter(|(linked, _)| linked == version)
+                .map(|(_, go_root)| go_root.clone())
+        }
     }
 
     #[test]
@@ -231,6 +257,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn marks_linked_versions() {
+        let ctx = MockCtx {
+            installed: vec!["1.22.3".to_string()],
+            linked: Some(("1.22.3".to_string(), PathBuf::from("/usr/lib/go"))),
+            ..MockCtx::default()
+        };
+        let mut out: Vec<u8> = Vec::new();
+
+        list_versions(&ctx, "go", &mut out).unwrap();
+
+        let output = String::from_utf8(out).unwrap();
+        assert!(output.contains("  1.22.3 (linked: /usr/lib/go)"));
+    }
+
     #[test]
     fn marks_pinned_version_as_active() {
         let ctx = MockCtx {
@@ -253,6 +294,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn shows_floating_spec_with_resolved_version() {
+        let ctx = MockCtx {
+            default: Some("stable".to_string()),
+            pinned: Some("1.21".to_string()),
+            installed: vec![
+                "1.21.2".to_string(),
+                "1.21.5".to_string(),
+                "1.22.3".to_string(),
+                "1.23rc1".to_string(),
+            ],
+            ..MockCtx::default()
+        };
+        let mut out: Vec<u8> = Vec::new();
+
+        list_versions(&ctx, "go", &mut out).unwrap();
+
+        let output = String::from_utf8(out).unwrap();
+        assert!(output.contains("  1.22.3 (default: stable)"));
+        assert!(output.contains("* 1.21.5 (pinned: 1.21)"));
+        assert!(output.contains("  1.21.2\n"));
+    }
+
     #[test]
     fn normalizes_v_prefix() {
         assert_eq!(normalize_version("v1.2.3"), "1.2.3");
@@ -1,11 +1,16 @@
 use crate::cli::install::get_tool_info;
-use crate::shared::versions::{fetch_remote_versions, GoVersionInfo};
+use crate::shared::active_version::find_active_go_version;
+use crate::shared::tool_compat::{fetch_go_requirements, GoRequirement};
+use crate::shared::versions::{fetch_remote_versions, fetch_tool_versions, GoVersionInfo};
 use std::error::Error;
 use std::fs;
 use std::future::Future;
 use std::io::Write;
 use std::path::PathBuf;
 
+/// How many of the newest tool versions get a compatibility annotation.
+const COMPATIBILITY_ROWS: usize = 10;
+
 pub async fn run(tool_opt: Option<String>) {
     let tool = tool_opt.unwrap_or_else(|| "go".to_string());
 
@@ -31,6 +36,10 @@ pub async fn run(tool_opt: Option<String>) {
                 let fetcher = || fetch_tool_versions(module.clone());
                 if let Err(e) = list_remote_versions(&tool, fetcher, &cache, &mut out).await {
                     eprintln!("Error: {}", e);
+                    return;
+                }
+                if let Err(e) = show_compatibility(&module, &cache, &mut out).await {
+                    eprintln!("Error: {}", e);
                 }
             }
             None => {
@@ -95,26 +104,64 @@ where
     }
 }
 
-async fn fetch_tool_versions(package: String) -> Result<Vec<GoVersionInfo>, Box<dyn Error>> {
-    let url = format!("https://proxy.golang.org/{}/@v/list", package);
-    let response = reqwest::get(&url).await?.error_for_status()?.text().await?;
+/// Annotates the newest stable tool versions with the Go version their go.mod requires.
+async fn show_compatibility(
+    module: &str,
+    cache: &impl RemoteVersionsCache,
+    out: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    let active_go = match find_active_go_version()? {
+        Some(version) => version.trim_start_matches("go@").to_string(),
+        None => return Ok(()),
+    };
 
-    let mut versions: Vec<GoVersionInfo> = response
-        .lines()
-        .filter(|line| !line.trim().is_empty())
-        .map(|line| {
-            let v = line.trim();
-            let stable = !v.contains("rc") && !v.contains("beta") && !v.contains("alpha");
-            GoVersionInfo {
-                version: v.to_string(),
-                stable,
-            }
-        })
+    let versions: Vec<String> = cache
+        .read_cache()?
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|v| v.stable)
+        .take(COMPATIBILITY_ROWS)
+        .map(|v| v.version)
         .collect();
+    let requirements = fetch_go_requirements(module, &versions).await;
+
+    render_compatibility(&versions, &requirements, &active_go, out)
+}
+
+fn render_compatibility(
+    versions: &[String],
+    requirements: &[Option<GoRequirement>],
+    active_go: &str,
+    out: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    if versions.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(
+        out,
+        "\nCompatibility with the active Go version ({}):",
+        active_go
+    )?;
+    for (version, requirement) in versions.iter().zip(requirements) {
+        match requirement {
+            Some(req) => {
+                let required = req.go.as_deref().unwrap_or("any");
+                if req.is_satisfied_by(active_go) {
+                    writeln!(out, "  {}  requires Go {}", version, required)?;
+                } else {
+                    writeln!(
+                        out,
+                        "  {}  requires Go {} (incompatible)",
+                        version, required
+                    )?;
+                }
+            }
+            None => writeln!(out, "  {}  requirement unknown", version)?,
+        }
+    }
 
-    // Proxy list is typically oldest to newest. Reverse to show newest first.
-    versions.reverse();
-    Ok(versions)
+    Ok(())
 }
 
 fn render_versions(versions: &[GoVersionInfo], out: &mut impl Write) -> Result<(), Box<dyn Error>> {
@@ -306,6 +353,35 @@ mod tests {
         assert!(output.contains("  1.21.0"));
     }
 
+    #[test]
+    fn renders_compatibility_with_active_go() {
+        let versions = vec![
+            "v0.18.1".to_string(),
+            "v0.16.2".to_string(),
+            "v0.9.0".to_string(),
+        ];
+        let requirements = vec![
+            Some(GoRequirement {
+                go: Some("1.23.4".into()),
+                toolchain: None,
+            }),
+            Some(GoRequirement {
+                go: Some("1.22".into()),
+                toolchain: None,
+            }),
+            None,
+        ];
+        let mut out = Vec::new();
+
+        render_compatibility(&versions, &requirements, "1.22.3", &mut out).unwrap();
+
+        let output = String::from_utf8(out).unwrap();
+        assert!(output.contains("Compatibility with the active Go version (1.22.3):"));
+        assert!(output.contains("  v0.18.1  requires Go 1.23.4 (incompatible)"));
+        assert!(output.contains("  v0.16.2  requires Go 1.22\n"));
+        assert!(output.contains("  v0.9.0  requirement unknown"));
+    }
+
     #[derive(Default)]
     struct MockCache {
         stored: RefCell<Option<Vec<GoVersionInfo>>>,
@@ -1,12 +1,18 @@
 pub mod completions;
+pub mod config;
 pub mod default;
 pub mod exec;
+pub mod import;
 pub mod install;
+pub mod link;
 pub mod list;
 pub mod list_remote;
 pub mod pin;
 pub mod run;
 pub mod setup;
+pub mod status;
+pub mod tools;
 pub mod uninstall;
 pub mod unpin;
+pub mod use_version;
 pub mod which;
@@ -1,66 +1,277 @@
+use crate::cli::install::install_spec;
 use crate::shared::local_versions::get_installed_versions;
-use serde::{Deserialize, Serialize};
+use golta_core::go_mod::{find_go_version_file, parse_go_mod, GoWork};
+use golta_core::go_mod_edit::set_toolchain_version;
+use golta_core::gover::{compare_go_versions, select_version, GoVersion};
+use golta_core::pinned_version::set_pinned_version;
+use golta_core::project_file::{ProjectFile, PROJECT_FILE_NAME};
+use golta_core::system::is_system_version;
+use std::cmp::Ordering;
 use std::error::Error;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 
-#[derive(Serialize, Deserialize)]
-struct PinFile {
-    go: String,
-}
-
-pub fn run(tool: String, _update_go_mod: bool) {
+pub async fn run(tool: String, options: PinOptions) {
     let ctx = FsPinContext;
     let mut out = std::io::stdout();
-    if let Err(e) = pin_go_version(&ctx, &tool, &mut out) {
+    if options.install {
+        if let Some(spec) = tool.strip_prefix("go@") {
+            if let Err(e) = install_spec("go", spec, &mut out).await {
+                eprintln!("Error: {}", e);
+                return;
+            }
+        }
+    }
+    if let Err(e) = pin_go_version(&ctx, &tool, &options, &mut out) {
         eprintln!("Error: {}", e);
     }
 }
 
+/// Flags of `golta pin`.
+pub struct PinOptions {
+    /// Update the `go` and `toolchain` lines of go.mod or go.work.
+    pub update_go_mod: bool,
+    /// Store the installed version a floating spec resolves to instead of the spec.
+    pub exact: bool,
+    /// Install the newest release matching the spec before pinning it.
+    pub install: bool,
+    /// Write the pin file in the current directory instead of the project root.
+    pub here: bool,
+}
+
+impl Default for PinOptions {
+    fn default() -> Self {
+        Self {
+            update_go_mod: true,
+            exact: false,
+            install: false,
+            here: false,
+        }
+    }
+}
+
 fn pin_go_version(
     ctx: &impl PinContext,
     tool: &str,
+    options: &PinOptions,
     out: &mut dyn Write,
 ) -> Result<(), Box<dyn Error>> {
     if !tool.starts_with("go@") {
         return Err("Invalid format. Use `golta pin go@<version>`.".into());
     }
 
-    let version = tool.trim_start_matches("go@");
+    let spec = tool.trim_start_matches("go@");
 
-    // Check if the version is installed before pinning
-    let installed_versions = ctx.installed_versions()?;
-    if !installed_versions.iter().any(|v| v == version) {
-        return Err(format!(
-            "Go version '{}' is not installed. Please install it first with `golta install go@{}`.",
-            version, version
-        )
-        .into());
+    // The spec must match an installed version; `system` is not managed by golta.
+    // Floating specs such as `1.22` or `stable` are stored as written unless --exact.
+    let (version, resolved) = if is_system_version(spec) {
+        (spec.to_string(), spec.to_string())
+    } else {
+        let installed_versions = ctx.installed_versions()?;
+        let resolved = select_version(spec, installed_versions.iter().map(String::as_str))
+            .ok_or_else(|| {
+                format!(
+                    "Go version '{}' is not installed. Install it first with `golta install go@{}`, or pass --install.",
+                    spec, spec
+                )
+            })?
+            .to_string();
+        let version = if options.exact {
+            resolved.clone()
+        } else {
+            spec.to_string()
+        };
+        (version, resolved)
+    };
+
+    // Pin for the whole module or repository unless --here, so running pin in a
+    // subdirectory does not leave a stray .golta.json deep in the tree.
+    let current_dir = ctx.current_dir()?;
+    let project_dir = if options.here {
+        current_dir.clone()
+    } else {
+        project_root(ctx, &current_dir)
+    };
+    let pin_file = project_dir.join(PROJECT_FILE_NAME);
+
+    let existing = ctx.read_pin_file(&pin_file)?;
+    let contents = set_pinned_version(existing.as_deref(), "go", &version)?;
+    ctx.write_pin_file(&pin_file, &contents)?;
+
+    if version == resolved {
+        writeln!(
+            out,
+            "Pinned Go version {} to {}",
+            version,
+            pin_file.display()
+        )?;
+    } else {
+        writeln!(
+            out,
+            "Pinned Go version {} (currently {}) to {}",
+            version,
+            resolved,
+            pin_file.display()
+        )?;
     }
 
-    let project_dir = ctx.current_dir()?;
-    let pin_file = project_dir.join(".golta.json");
+    warn_about_other_pins(ctx, &current_dir, &project_dir, out)?;
+
+    if options.update_go_mod && !is_system_version(&resolved) {
+        update_go_version_file(ctx, &resolved, out)?;
+    }
+
+    Ok(())
+}
+
+/// Files and directories that mark the root of a module, workspace or repository.
+const PROJECT_ROOT_MARKERS: [&str; 4] = ["go.mod", "go.work", ".git", ".hg"];
+
+/// The nearest directory at or above `dir` that contains go.mod, go.work or a VCS
+/// directory, or `dir` itself when there is none.
+fn project_root(ctx: &impl PinContext, dir: &Path) -> PathBuf {
+    dir.ancestors()
+        .find(|ancestor| {
+            PROJECT_ROOT_MARKERS
+                .iter()
+                .any(|marker| ctx.exists(&ancestor.join(marker)))
+        })
+        .unwrap_or(dir)
+        .to_path_buf()
+}
+
+/// Warns about other `.golta.json` files that pin Go for the current directory: the one
+/// above the new pin that it now shadows, and any between it and the current directory
+/// that still take precedence over it.
+fn warn_about_other_pins(
+    ctx: &impl PinContext,
+    current_dir: &Path,
+    project_dir: &Path,
+    out: &mut dyn Write,
+) -> Result<(), Box<dyn Error>> {
+    for dir in current_dir.ancestors().filter(|dir| *dir != project_dir) {
+        let path = dir.join(PROJECT_FILE_NAME);
+        let Some(content) = ctx.read_pin_file(&path)? else {
+            continue;
+        };
+        let Some(go) = ProjectFile::parse(&content).ok().and_then(|file| file.go) else {
+            continue;
+        };
+        if dir.starts_with(project_dir) {
+            writeln!(
+                out,
+                "Warning: {} pins Go {} and still applies to the current directory.",
+                path.display(),
+                go
+            )?;
+        } else {
+            writeln!(
+                out,
+                "Warning: {} pins Go {}; the new pin shadows it in {}.",
+                path.display(),
+                go,
+                project_dir.display()
+            )?;
+            break;
+        }
+    }
+    Ok(())
+}
 
-    let pin = PinFile {
-        go: version.to_string(),
+/// Updates the go.mod or go.work whose `go` line applies here so that its toolchain
+/// matches the pin, and prints the change. Inside a workspace the go.work decides the
+/// toolchain, so that is the file that gets edited.
+fn update_go_version_file(
+    ctx: &impl PinContext,
+    version: &str,
+    out: &mut dyn Write,
+) -> Result<(), Box<dyn Error>> {
+    let Some((path, content)) = ctx.go_version_file()? else {
+        return Ok(());
     };
-    let contents = serde_json::to_string_pretty(&pin)?;
-    ctx.write_pin_file(&pin_file, &contents)?;
+    if GoVersion::parse(version).is_none() {
+        writeln!(
+            out,
+            "Not updating {}: {} is not a Go release.",
+            path.display(),
+            version
+        )?;
+        return Ok(());
+    }
+
+    let is_workspace = path.extension().is_some_and(|ext| ext == "work");
+    if is_workspace {
+        writeln!(
+            out,
+            "This module is part of the workspace {}; its `go` line applies instead of go.mod.",
+            path.display()
+        )?;
+    }
+
+    if let Some(required) = parse_go_mod(&content).go {
+        if compare_go_versions(&required, version) == Ordering::Greater {
+            writeln!(
+                out,
+                "Warning: {} requires go {}, which is newer than the pinned {}; leaving it unchanged. Pin go@{} or newer to use this module.",
+                path.display(),
+                required,
+                version,
+                required
+            )?;
+            return Ok(());
+        }
+    }
 
-    writeln!(
-        out,
-        "Pinned Go version {} to {}",
-        version,
-        pin_file.display()
-    )?;
+    let updated = set_toolchain_version(&content, version);
+    if updated == content {
+        return Ok(());
+    }
+    ctx.write_go_version_file(&path, &updated)?;
+    writeln!(out, "Updated {}:", path.display())?;
+    write_diff(&content, &updated, out)
+}
 
+/// Prints the lines removed from `old` and added in `new`, based on their longest common
+/// subsequence. go.mod files are small enough for the quadratic table.
+fn write_diff(old: &str, new: &str, out: &mut dyn Write) -> Result<(), Box<dyn Error>> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    let mut common = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            i += 1;
+            j += 1;
+        } else if j == new.len() || (i < old.len() && common[i + 1][j] >= common[i][j + 1]) {
+            writeln!(out, "  -{}", old[i])?;
+            i += 1;
+        } else {
+            writeln!(out, "  +{}", new[j])?;
+            j += 1;
+        }
+    }
     Ok(())
 }
 
 trait PinContext {
     fn installed_versions(&self) -> Result<Vec<String>, Box<dyn Error>>;
     fn current_dir(&self) -> Result<PathBuf, Box<dyn Error>>;
+    fn exists(&self, path: &Path) -> bool;
+    fn read_pin_file(&self, path: &Path) -> Result<Option<String>, Box<dyn Error>>;
     fn write_pin_file(&self, path: &Path, contents: &str) -> Result<(), Box<dyn Error>>;
+    /// The go.work or go.mod that applies to the current directory, with its contents.
+    fn go_version_file(&self) -> Result<Option<(PathBuf, String)>, Box<dyn Error>>;
+    fn write_go_version_file(&self, path: &Path, contents: &str) -> Result<(), Box<dyn Error>>;
 }
 
 struct FsPinContext;
@@ -74,9 +285,36 @@ impl PinContext for FsPinContext {
         Ok(std::env::current_dir()?)
     }
 
+    fn exists(&self, path: &Path) -> bool {
+        path.exists()
+    }
+
+    fn read_pin_file(&self, path: &Path) -> Result<Option<String>, Box<dyn Error>> {
+        if path.exists() {
+            Ok(Some(std::fs::read_to_string(path)?))
+        } else {
+            Ok(None)
+        }
+    }
+
     fn write_pin_file(&self, path: &Path, contents: &str) -> Result<(), Box<dyn Error>> {
         Ok(std::fs::write(path, contents)?)
     }
+
+    fn go_version_file(&self) -> Result<Option<(PathBuf, String)>, Box<dyn Error>> {
+        let start = std::env::current_dir()?;
+        match find_go_version_file(&start, &GoWork::from_env()) {
+            Some(path) if path.is_file() => {
+                let content = std::fs::read_to_string(&path)?;
+                Ok(Some((path, content)))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn write_go_version_file(&self, path: &Path, contents: &str) -> Result<(), Box<dyn Error>> {
+        Ok(std::fs::write(path, contents)?)
+    }
 }
 
 #[cfg(test)]
@@ -87,8 +325,12 @@ mod tests {
     struct MockPinContext {
         installed: Vec<String>,
         current_dir: PathBuf,
+        existing: Option<String>,
         written: RefCell<Vec<(PathBuf, String)>>,
         fail_write: bool,
+        go_file: Option<(PathBuf, String)>,
+        files: Vec<PathBuf>,
+        other_pins: Vec<(PathBuf, String)>,
     }
 
     impl Default for MockPinContext {
@@ -96,8 +338,12 @@ mod tests {
             Self {
                 installed: Vec::new(),
                 current_dir: PathBuf::from("/tmp/project"),
+                existing: None,
                 written: RefCell::new(Vec::new()),
                 fail_write: false,
+                go_file: None,
+                files: Vec::new(),
+                other_pins: Vec::new(),
             }
         }
     }
@@ -111,6 +357,21 @@ mod tests {
             Ok(self.current_dir.clone())
         }
 
+        fn exists(&self, path: &Path) -> bool {
+            self.files.iter().any(|file| file == path)
+        }
+
+        fn read_pin_file(&self, path: &Path) -> Result<Option<String>, Box<dyn Error>> {
+            if path.parent() == Some(self.current_dir.as_path()) {
+                return Ok(self.existing.clone());
+            }
+            Ok(self
+                .other_pins
+                .iter()
+                .find(|(pin, _)| pin == path)
+                .map(|(_, content)| content.clone()))
+        }
+
         fn write_pin_file(&self, path: &Path, contents: &str) -> Result<(), Box<dyn Error>> {
             if self.fail_write {
                 return Err("write failed".into());
@@ -120,6 +381,17 @@ mod tests {
                 .push((path.to_path_buf(), contents.to_string()));
             Ok(())
         }
+
+        fn go_version_file(&self) -> Result<Option<(PathBuf, String)>, Box<dyn Error>> {
+            Ok(self.go_file.clone())
+        }
+
+        fn write_go_version_file(&self, path: &Path, contents: &str) -> Result<(), Box<dyn Error>> {
+            self.written
+                .borrow_mut()
+                .push((path.to_path_buf(), contents.to_string()));
+            Ok(())
+        }
     }
 
     #[test]
@@ -127,7 +399,7 @@ mod tests {
         let ctx = MockPinContext::default();
         let mut out: Vec<u8> = Vec::new();
 
-        let err = pin_go_version(&ctx, "node@1.0.0", &mut out).unwrap_err();
+        let err = pin_go_version(&ctx, "node@1.0.0", &PinOptions::default(), &mut out).unwrap_err();
 
         assert!(err
             .to_string()
@@ -139,7 +411,7 @@ mod tests {
         let ctx = MockPinContext::default();
         let mut out: Vec<u8> = Vec::new();
 
-        let err = pin_go_version(&ctx, "go@1.20.0", &mut out).unwrap_err();
+        let err = pin_go_version(&ctx, "go@1.20.0", &PinOptions::default(), &mut out).unwrap_err();
 
         assert!(err
             .to_string()
@@ -154,7 +426,7 @@ mod tests {
         };
         let mut out: Vec<u8> = Vec::new();
 
-        pin_go_version(&ctx, "go@1.21.0", &mut out).unwrap();
+        pin_go_version(&ctx, "go@1.21.0", &PinOptions::default(), &mut out).unwrap();
 
         let written = ctx.written.borrow();
         assert_eq!(written.len(), 1);
@@ -165,4 +437,162 @@ mod tests {
         let output = String::from_utf8(out).unwrap();
         assert!(output.contains("Pinned Go version 1.21.0 to"));
     }
+
+    #[test]
+    fn pins_at_module_root_and_warns_about_shadowed_pin() {
+        let ctx = MockPinContext {
+            installed: vec!["1.22.3".to_string()],
+            current_dir: PathBuf::from("/work/repo/internal/foo"),
+            files: vec![
+                PathBuf::from("/work/repo/go.mod"),
+                PathBuf::from("/work/repo/.git"),
+            ],
+            other_pins: vec![(
+                PathBuf::from("/work/.golta.json"),
+                r#"{"go":"1.21.0"}"#.to_string(),
+            )],
+            ..MockPinContext::default()
+        };
+        let mut out: Vec<u8> = Vec::new();
+
+        pin_go_version(&ctx, "go@1.22.3", &PinOptions::default(), &mut out).unwrap();
+        pin_go_version(
+            &ctx,
+            "go@1.22.3",
+            &PinOptions {
+                here: true,
+                ..PinOptions::default()
+            },
+            &mut out,
+        )
+        .unwrap();
+
+        let written = ctx.written.borrow();
+        assert_eq!(written[0].0, PathBuf::from("/work/repo/.golta.json"));
+        assert_eq!(
+            written[1].0,
+            PathBuf::from("/work/repo/internal/foo/.golta.json")
+        );
+        let output = String::from_utf8(out).unwrap();
+        assert!(output.contains(
+            "Warning: /work/.golta.json pins Go 1.21.0; the new pin shadows it in /work/repo."
+        ));
+    }
+
+    #[test]
+    fn keeps_tool_pins_when_repinning_go() {
+        let ctx = MockPinContext {
+            installed: vec!["1.22.3".to_string()],
+            existing: Some(r#"{"go":"1.21.0","tools":{"gopls":"v0.16.1"}}"#.to_string()),
+            ..MockPinContext::default()
+        };
+        let mut out: Vec<u8> = Vec::new();
+
+        pin_go_version(&ctx, "go@1.22.3", &PinOptions::default(), &mut out).unwrap();
+
+        let written = ctx.written.borrow();
+        let (_, contents) = &written[0];
+        assert!(contents.contains("1.22.3"));
+        assert!(contents.contains("v0.16.1"));
+    }
+
+    #[test]
+    fn stores_floating_spec_unless_exact() {
+        let ctx = MockPinContext {
+            installed: vec!["1.22.1".to_string(), "1.22.5".to_string()],
+            ..MockPinContext::default()
+        };
+        let mut out: Vec<u8> = Vec::new();
+
+        pin_go_version(&ctx, "go@1.22", &PinOptions::default(), &mut out).unwrap();
+        pin_go_version(
+            &ctx,
+            "go@1.22",
+            &PinOptions {
+                exact: true,
+                ..PinOptions::default()
+            },
+            &mut out,
+        )
+        .unwrap();
+
+        let written = ctx.written.borrow();
+        assert!(written[0].1.contains("\"1.22\""));
+        assert!(written[1].1.contains("\"1.22.5\""));
+        let output = String::from_utf8(out).unwrap();
+        assert!(output.contains("Pinned Go version 1.22 (currently 1.22.5)"));
+    }
+
+    #[test]
+    fn reports_workspace_go_work_and_newer_go_line() {
+        let ctx = MockPinContext {
+            installed: vec!["1.22.3".to_string()],
+            go_file: Some((
+                PathBuf::from("/tmp/go.work"),
+                "go 1.23.0\n\nuse ./project\n".to_string(),
+            )),
+            ..MockPinContext::default()
+        };
+        let mut out: Vec<u8> = Vec::new();
+
+        pin_go_version(&ctx, "go@1.22.3", &PinOptions::default(), &mut out).unwrap();
+
+        let output = String::from_utf8(out).unwrap();
+        assert!(output.contains("part of the workspace /tmp/go.work"));
+        assert!(output.contains(
+            "requires go 1.23.0, which is newer than the pinned 1.22.3; leaving it unchanged. Pin go@1.23.0 or newer"
+        ));
+        assert!(!output.contains("Updated /tmp/go.work"));
+        let written = ctx.written.borrow();
+        assert_eq!(written.len(), 1);
+        assert!(written[0].0.ends_with(PROJECT_FILE_NAME));
+    }
+
+    #[test]
+    fn adds_toolchain_to_go_mod_and_shows_diff() {
+        let ctx = MockPinContext {
+            installed: vec!["1.22.3".to_string()],
+            go_file: Some((
+                PathBuf::from("/tmp/project/go.mod"),
+                "module m\n\ngo 1.21 // language\n".to_string(),
+            )),
+            ..MockPinContext::default()
+        };
+        let mut out: Vec<u8> = Vec::new();
+
+        pin_go_version(&ctx, "go@1.22.3", &PinOptions::default(), &mut out).unwrap();
+
+        let output = String::from_utf8(out).unwrap();
+        assert!(output.contains("Updated /tmp/project/go.mod:\n  +toolchain go1.22.3\n"));
+        assert_eq!(
+            ctx.written.borrow()[1].1,
+            "module m\n\ngo 1.21 // language\ntoolchain go1.22.3\n"
+        );
+    }
+
+    #[test]
+    fn skips_go_mod_when_disabled() {
+        let ctx = MockPinContext {
+            installed: vec!["1.22.3".to_string()],
+            go_file: Some((
+                PathBuf::from("/tmp/project/go.mod"),
+                "module m\n\ngo 1.21\n".to_string(),
+            )),
+            ..MockPinContext::default()
+        };
+        let mut out: Vec<u8> = Vec::new();
+
+        pin_go_version(
+            &ctx,
+            "go@1.22.3",
+            &PinOptions {
+                update_go_mod: false,
+                ..PinOptions::default()
+            },
+            &mut out,
+        )
+        .unwrap();
+
+        assert_eq!(ctx.written.borrow().len(), 1);
+    }
 }
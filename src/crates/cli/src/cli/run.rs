@@ -1,3 +1,5 @@
+use golta_core::system::{default_shim_path, find_system_program, is_system_version};
+use std::env;
 use std::error::Error;
 use std::path::{Path, PathBuf};
 use std::process::Command;
@@ -29,6 +31,11 @@ fn run_go(
         return Err("Only Go run is supported currently. Use format 'go@<version>'.".into());
     }
 
+    if is_system_version(version) {
+        let go_path = env.system_go().ok_or("No system `go` was found on PATH.")?;
+        return runner.run(&go_path, args);
+    }
+
     let go_path = env.go_binary_path(version)?;
     if !env.binary_exists(&go_path) {
         return Err(format!(
@@ -43,6 +50,8 @@ fn run_go(
 trait GoRunEnvironment {
     fn go_binary_path(&self, version: &str) -> Result<PathBuf, Box<dyn Error>>;
     fn binary_exists(&self, path: &Path) -> bool;
+    /// The system Go on PATH, for `go@system`.
+    fn system_go(&self) -> Option<PathBuf>;
 }
 
 struct RealGoRunEnvironment;
@@ -63,6 +72,16 @@ impl GoRunEnvironment for RealGoRunEnvironment {
     fn binary_exists(&self, path: &Path) -> bool {
         path.exists()
     }
+
+    fn system_go(&self) -> Option<PathBuf> {
+        let home = home::home_dir()?;
+        find_system_program(
+            "go",
+            env::var_os("PATH").as_deref(),
+            &home,
+            &[default_shim_path(&home)],
+        )
+    }
 }
 
 trait GoCommandRunner {
@@ -106,6 +125,10 @@ mod tests {
         fn binary_exists(&self, _path: &Path) -> bool {
             true
         }
+
+        fn system_go(&self) -> Option<PathBuf> {
+            Some(PathBuf::from("/usr/local/go/bin/go"))
+        }
     }
 
     struct MockRunner {
@@ -169,4 +192,18 @@ mod tests {
         assert_eq!(runner.last_path.unwrap(), PathBuf::from("/tmp/go/bin/go"));
         assert_eq!(runner.last_args, args);
     }
+
+    #[test]
+    fn system_version_runs_the_system_go() {
+        let env = MockEnv::new("/tmp/go/bin/go");
+        let mut runner = MockRunner::new(0);
+
+        run_go("go@system", &["version".into()], &env, &mut runner).unwrap();
+
+        assert_eq!(
+            runner.last_path.unwrap(),
+            PathBuf::from("/usr/local/go/bin/go")
+        );
+        assert_eq!(*env.requested_version.borrow(), None);
+    }
 }
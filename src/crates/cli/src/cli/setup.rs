@@ -1,5 +1,44 @@
+use golta_core::system::is_same_file;
 use std::error::Error;
+use std::fs;
 use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Toolchain binaries served by linking the `go` shim under their name.
+const SHIM_LINKS: &[&str] = &["gofmt"];
+
+/// Shell function that lets `golta use` change the environment of the calling shell.
+const SH_INTEGRATION: &str = r#"# Golta shell integration (generated by `golta setup`)
+golta() {
+  if [ "$1" = "use" ]; then
+    shift
+    eval "$(command golta use --shell sh "$@")"
+  else
+    command golta "$@"
+  fi
+}
+"#;
+
+const FISH_INTEGRATION: &str = r#"# Golta shell integration (generated by `golta setup`)
+function golta
+  if test "$argv[1]" = use
+    command golta use --shell fish $argv[2..-1] | source
+  else
+    command golta $argv
+  end
+end
+"#;
+
+const POWERSHELL_INTEGRATION: &str = r#"# Golta shell integration (generated by `golta setup`)
+function golta {
+  if ($args.Count -gt 0 -and $args[0] -eq 'use') {
+    $rest = @($args | Select-Object -Skip 1)
+    golta.exe use --shell powershell @rest | Out-String | Invoke-Expression
+  } else {
+    golta.exe @args
+  }
+}
+"#;
 
 pub fn run() {
     let mut stdout = io::stdout();
@@ -11,10 +50,10 @@ pub fn run() {
 fn setup_environment<W: Write>(writer: &mut W) -> Result<(), Box<dyn Error>> {
     writeln!(writer, "Configuring your shell for Golta...")?;
 
-    let cargo_bin = home::home_dir()
-        .ok_or("Could not find home directory")?
-        .join(".cargo")
-        .join("bin");
+    let home = home::home_dir().ok_or("Could not find home directory")?;
+    let cargo_bin = home.join(".cargo").join("bin");
+    let shell_dir = write_shell_integration(&home.join(".golta"))?;
+    link_shim_binaries(&cargo_bin, writer)?;
 
     writeln!(
         writer,
@@ -32,6 +71,15 @@ fn setup_environment<W: Write>(writer: &mut W) -> Result<(), Box<dyn Error>> {
             "\nPlease add the following directory to your user PATH environment variable:"
         )?;
         writeln!(writer, "  {}", cargo_bin.display())?;
+        writeln!(
+            writer,
+            "\nTo let `golta use` switch versions in the current session, add this line to your PowerShell profile ($PROFILE):"
+        )?;
+        writeln!(
+            writer,
+            "\n  . \"{}\"",
+            shell_dir.join("golta.ps1").display()
+        )?;
         writeln!(
             writer,
             "\nYou will need to restart your terminal for the changes to take effect."
@@ -45,6 +93,16 @@ fn setup_environment<W: Write>(writer: &mut W) -> Result<(), Box<dyn Error>> {
             "\nPlease add the following line to your shell's startup file (e.g., ~/.bashrc, ~/.zshrc):"
         )?;
         writeln!(writer, "\n  export PATH=\"{}:$PATH\"", cargo_bin.display())?;
+        writeln!(
+            writer,
+            "\nTo let `golta use` switch versions in the current session, also add:"
+        )?;
+        writeln!(writer, "\n  . \"{}\"", shell_dir.join("golta.sh").display())?;
+        writeln!(
+            writer,
+            "\nFor fish, add `source {}` to ~/.config/fish/config.fish instead.",
+            shell_dir.join("golta.fish").display()
+        )?;
         writeln!(
             writer,
             "\nAfter adding the line, restart your terminal or run `source <your_shell_file>`."
@@ -55,3 +113,142 @@ fn setup_environment<W: Write>(writer: &mut W) -> Result<(), Box<dyn Error>> {
 
     Ok(())
 }
+
+/// Writes the shell integration scripts to `<golta_dir>/shell` and returns that directory.
+fn write_shell_integration(golta_dir: &Path) -> Result<PathBuf, Box<dyn Error>> {
+    let shell_dir = golta_dir.join("shell");
+    fs::create_dir_all(&shell_dir)?;
+    fs::write(shell_dir.join("golta.sh"), SH_INTEGRATION)?;
+    fs::write(shell_dir.join("golta.fish"), FISH_INTEGRATION)?;
+    fs::write(shell_dir.join("golta.ps1"), POWERSHELL_INTEGRATION)?;
+    Ok(shell_dir)
+}
+
+/// Creates or refreshes links to the `go` shim (e.g. `gofmt`) in `bin_dir`. The shim
+/// dispatches on the name it was invoked as.
+fn link_shim_binaries<W: Write>(bin_dir: &Path, writer: &mut W) -> Result<(), Box<dyn Error>> {
+    let shim = bin_dir.join(executable_name("go"));
+    if !shim.is_file() {
+        writeln!(
+            writer,
+            "\nThe go shim was not found at {}; skipping links for {}.",
+            shim.display(),
+            SHIM_LINKS.join(", ")
+        )?;
+        return Ok(());
+    }
+
+    for name in SHIM_LINKS {
+        let link = bin_dir.join(executable_name(name));
+        if let Ok(meta) = fs::symlink_metadata(&link) {
+            // Replace our own links; leave a real binary someone else put there alone.
+            if !meta.file_type().is_symlink() && !is_shim_copy(&link, &shim) {
+                writeln!(
+                    writer,
+                    "\nWarning: {} already exists and is not a link to the shim; leaving it alone.",
+                    link.display()
+                )?;
+                continue;
+            }
+            fs::remove_file(&link)?;
+        }
+        create_link(&shim, &link)?;
+        writeln!(writer, "\nLinked {} to the go shim.", link.display())?;
+    }
+    Ok(())
+}
+
+/// Whether `link` is a hard link or copy of `shim`, as `create_link` makes on Windows.
+fn is_shim_copy(link: &Path, shim: &Path) -> bool {
+    if is_same_file(link, shim) {
+        return true;
+    }
+    let same_size = match (fs::metadata(link), fs::metadata(shim)) {
+        (Ok(a), Ok(b)) => a.len() == b.len(),
+        _ => false,
+    };
+    same_size && matches!((fs::read(link), fs::read(shim)), (Ok(a), Ok(b)) if a == b)
+}
+
+fn executable_name(name: &str) -> String {
+    if cfg!(windows) {
+        format!("{}.exe", name)
+    } else {
+        name.to_string()
+    }
+}
+
+#[cfg(unix)]
+fn create_link(shim: &Path, link: &Path) -> Result<(), Box<dyn Error>> {
+    std::os::unix::fs::symlink(shim, link)?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn create_link(shim: &Path, link: &Path) -> Result<(), Box<dyn Error>> {
+    // Symlinks need extra privileges on Windows; hard links and copies do not.
+    if fs::hard_link(shim, link).is_err() {
+        fs::copy(shim, link)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn writes_shell_integration_scripts() {
+        let dir = tempdir().unwrap();
+
+        let shell_dir = write_shell_integration(dir.path()).unwrap();
+
+        let sh = fs::read_to_string(shell_dir.join("golta.sh")).unwrap();
+        assert!(sh.contains("command golta use --shell sh"));
+        assert!(shell_dir.join("golta.fish").exists());
+        assert!(shell_dir.join("golta.ps1").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn links_and_refreshes_gofmt() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("go"), "shim").unwrap();
+        let mut out = Vec::new();
+
+        link_shim_binaries(dir.path(), &mut out).unwrap();
+        link_shim_binaries(dir.path(), &mut out).unwrap();
+
+        let target = fs::read_link(dir.path().join("gofmt")).unwrap();
+        assert_eq!(target, dir.path().join("go"));
+    }
+
+    #[test]
+    fn keeps_foreign_gofmt() {
+        let dir = tempdir().unwrap();
+        let gofmt = dir.path().join(executable_name("gofmt"));
+        fs::write(dir.path().join(executable_name("go")), "shim").unwrap();
+        fs::write(&gofmt, "real gofmt").unwrap();
+        let mut out = Vec::new();
+
+        link_shim_binaries(dir.path(), &mut out).unwrap();
+
+        assert_eq!(fs::read_to_string(&gofmt).unwrap(), "real gofmt");
+        assert!(String::from_utf8(out).unwrap().contains("leaving it alone"));
+    }
+
+    #[test]
+    fn replaces_copy_of_the_shim() {
+        let dir = tempdir().unwrap();
+        let gofmt = dir.path().join(executable_name("gofmt"));
+        fs::write(dir.path().join(executable_name("go")), "shim").unwrap();
+        fs::write(&gofmt, "shim").unwrap();
+        let mut out = Vec::new();
+
+        link_shim_binaries(dir.path(), &mut out).unwrap();
+
+        assert!(String::from_utf8(out).unwrap().contains("Linked"));
+        assert_eq!(fs::read_to_string(&gofmt).unwrap(), "shim");
+    }
+}
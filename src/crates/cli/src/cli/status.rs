@@ -0,0 +1,221 @@
+use crate::shared::local_versions::get_installed_versions;
+use golta_core::gover::{select_minimum, select_version};
+use golta_core::resolve::{find_go_version, Resolution};
+use golta_core::system::{default_shim_path, find_system_program, is_system_version};
+use std::env;
+use std::error::Error;
+use std::io::Write;
+use std::path::PathBuf;
+
+pub fn run() {
+    let ctx = FsStatusContext;
+    let mut out = std::io::stdout();
+    if let Err(e) = show_status(&ctx, &mut out) {
+        eprintln!("Error: {}", e);
+    }
+}
+
+fn show_status(ctx: &impl StatusContext, out: &mut dyn Write) -> Result<(), Box<dyn Error>> {
+    let resolution = match ctx.resolve()? {
+        Some(resolution) => resolution,
+        None => {
+            writeln!(
+                out,
+                "No Go version is active. Use `golta pin go@<version>` in your project, or `golta default go@<version>` globally."
+            )?;
+            return Ok(());
+        }
+    };
+
+    let version = resolution.version.trim_start_matches("go@");
+    writeln!(out, "Go version: {}", version)?;
+    writeln!(out, "Source:     {}", resolution.describe_source())?;
+
+    if is_system_version(version) {
+        match ctx.system_go() {
+            Some(path) => writeln!(out, "Installed:  system ({})", path.display())?,
+            None => writeln!(out, "Installed:  no (no system `go` found on PATH)")?,
+        }
+        return Ok(());
+    }
+
+    let installed = ctx.installed_versions()?;
+    let installed = installed.iter().map(String::as_str);
+    let selected = if resolution.source.is_minimum() {
+        select_minimum(version, installed)
+    } else {
+        select_version(version, installed)
+    };
+    match selected {
+        Some(selected) if selected == version => writeln!(out, "Installed:  yes")?,
+        Some(selected) => writeln!(out, "Installed:  yes (using {})", selected)?,
+        None => {
+            writeln!(out, "Installed:  no (run `golta install go@{}`)", version)?;
+        }
+    }
+
+    Ok(())
+}
+
+trait StatusContext {
+    fn resolve(&self) -> Result<Option<Resolution>, Box<dyn Error>>;
+    fn installed_versions(&self) -> Result<Vec<String>, Box<dyn Error>>;
+    fn system_go(&self) -> Option<PathBuf>;
+}
+
+struct FsStatusContext;
+
+impl StatusContext for FsStatusContext {
+    fn resolve(&self) -> Result<Option<Resolution>, Box<dyn Error>> {
+        let home = home::home_dir().ok_or("Could not find home directory")?;
+        find_go_version(&std::env::current_dir()?, &home)
+    }
+
+    fn installed_versions(&self) -> Result<Vec<String>, Box<dyn Error>> {
+        get_installed_versions()
+    }
+
+    fn system_go(&self) -> Option<PathBuf> {
+        let home = home::home_dir()?;
+        find_system_program(
+            "go",
+            env::var_os("PATH").as_deref(),
+            &home,
+            &[default_shim_path(&home)],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use golta_core::resolve::VersionSource;
+
+    struct MockCtx {
+        resolution: Option<Resolution>,
+        installed: Vec<String>,
+    }
+
+    impl StatusContext for MockCtx {
+        fn resolve(&self) -> Result<Option<Resolution>, Box<dyn Error>> {
+            Ok(self.resolution.clone())
+        }
+
+        fn installed_versions(&self) -> Result<Vec<String>, Box<dyn Error>> {
+            Ok(self.installed.clone())
+        }
+
+        fn system_go(&self) -> Option<PathBuf> {
+            Some(PathBuf::from("/usr/local/go/bin/go"))
+        }
+    }
+
+    #[test]
+    fn reports_winning_file() {
+        let ctx = MockCtx {
+            resolution: Some(Resolution {
+                version: "1.22.3".to_string(),
+                source: VersionSource::GoVersionFile,
+                path: Some(PathBuf::from("/work/app/.go-version")),
+                line: None,
+            }),
+            installed: vec!["1.22.3".to_string()],
+        };
+        let mut out: Vec<u8> = Vec::new();
+
+        show_status(&ctx, &mut out).unwrap();
+
+        let output = String::from_utf8(out).unwrap();
+        assert!(output.contains("Go version: 1.22.3"));
+        assert!(output.contains("Source:     .go-version (/work/app/.go-version)"));
+        assert!(output.contains("Installed:  yes"));
+    }
+
+    #[test]
+    fn reports_environment_override() {
+        let ctx = MockCtx {
+            resolution: Some(Resolution {
+                version: "1.21.0".to_string(),
+                source: VersionSource::Environment,
+                path: None,
+                line: None,
+            }),
+            installed: vec!["1.21.0".to_string()],
+        };
+        let mut out: Vec<u8> = Vec::new();
+
+        show_status(&ctx, &mut out).unwrap();
+
+        assert!(String::from_utf8(out)
+            .unwrap()
+            .contains("Source:     environment (GOLTA_GO_VERSION)"));
+    }
+
+    #[test]
+    fn reports_toolchain_selected_for_go_mod_minimum() {
+        let ctx = MockCtx {
+            resolution: Some(Resolution {
+                version: "1.22.3".to_string(),
+                source: VersionSource::GoMod,
+                path: Some(PathBuf::from("/work/app/go.mod")),
+                line: None,
+            }),
+            installed: vec!["1.22.1".to_string(), "1.22.4".to_string()],
+        };
+        let mut out: Vec<u8> = Vec::new();
+
+        show_status(&ctx, &mut out).unwrap();
+
+        assert!(String::from_utf8(out)
+            .unwrap()
+            .contains("Installed:  yes (using 1.22.4)"));
+    }
+
+    #[test]
+    fn reports_system_go() {
+        let ctx = MockCtx {
+            resolution: Some(Resolution {
+                version: "system".to_string(),
+                source: VersionSource::Default,
+                path: None,
+                line: None,
+            }),
+            installed: Vec::new(),
+        };
+        let mut out: Vec<u8> = Vec::new();
+
+        show_status(&ctx, &mut out).unwrap();
+
+        assert!(String::from_utf8(out)
+            .unwrap()
+            .contains("Installed:  system (/usr/local/go/bin/go)"));
+    }
+
+    #[test]
+    fn reports_missing_install_and_no_version() {
+        let ctx = MockCtx {
+            resolution: Some(Resolution {
+                version: "1.21.0".to_string(),
+                source: VersionSource::GoMod,
+                path: Some(PathBuf::from("/work/app/go.mod")),
+                line: None,
+            }),
+            installed: Vec::new(),
+        };
+        let mut out: Vec<u8> = Vec::new();
+        show_status(&ctx, &mut out).unwrap();
+        assert!(String::from_utf8(out)
+            .unwrap()
+            .contains("Installed:  no (run `golta install go@1.21.0`)"));
+
+        let ctx = MockCtx {
+            resolution: None,
+            installed: Vec::new(),
+        };
+        let mut out: Vec<u8> = Vec::new();
+        show_status(&ctx, &mut out).unwrap();
+        assert!(String::from_utf8(out)
+            .unwrap()
+            .contains("No Go version is active."));
+    }
+}
@@ -0,0 +1,553 @@
+use crate::cli::install::{build_tool, get_tool_info, install_go_package, BuildGo};
+use crate::shared::active_version::find_active_go_version;
+use crate::shared::local_versions::get_installed_tool_versions;
+use crate::shared::tool_builds::{
+    list_installed_tools, write_build_info, InstalledTool, ToolBuildInfo,
+};
+use crate::{ToolsCommand, ToolsCommands};
+use golta_core::go_mod::{find_module_root, parse_go_mod};
+use golta_core::pinned_version::{find_pinned_tool_version_in, set_pinned_version};
+use golta_core::system::is_system_version;
+use std::error::Error;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Locations searched for a `tools.go` file with blank imports, relative to the module root.
+const TOOLS_GO_CANDIDATES: [&str; 3] = ["tools.go", "tools/tools.go", "internal/tools/tools.go"];
+
+pub async fn run(cmd: ToolsCommand) {
+    let mut stdout = io::stdout();
+    let result = match cmd.command {
+        ToolsCommands::Sync { check } => sync_tools(check, &mut stdout).await,
+        ToolsCommands::Rebuild { go } => rebuild_tools(go.as_deref(), None, &mut stdout),
+    };
+
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+/// A tool package declared by the project, with the version taken from go.mod.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DeclaredTool {
+    name: String,
+    package: String,
+    module: String,
+    version: String,
+}
+
+async fn sync_tools(check: bool, writer: &mut impl Write) -> Result<(), Box<dyn Error>> {
+    let current_dir = std::env::current_dir()?;
+    let root = find_module_root(&current_dir)
+        .ok_or("No go.mod found in this directory or any parent directory.")?;
+
+    let go_mod = fs::read_to_string(root.join("go.mod"))?;
+    let tools_go_sources = read_tools_go_files(&root)?;
+    let declared = declared_tools(&go_mod, &tools_go_sources)?;
+
+    if declared.is_empty() {
+        writeln!(writer, "No tools are declared in go.mod or tools.go.")?;
+        return Ok(());
+    }
+
+    if check {
+        // Read the pins from the module root, where `sync` writes them.
+        let drift = find_drift(&declared, &FsToolState { root: root.clone() })?;
+        if drift.is_empty() {
+            writeln!(writer, "All {} declared tools are in sync.", declared.len())?;
+            return Ok(());
+        }
+        for problem in &drift {
+            writeln!(writer, "  {}", problem)?;
+        }
+        return Err(format!(
+            "{} tool(s) are out of sync with the project declarations. Run `golta tools sync`.",
+            drift.len()
+        )
+        .into());
+    }
+
+    let home = home::home_dir().ok_or("Could not find home directory")?;
+    for tool in &declared {
+        install_go_package(
+            &tool.name,
+            &tool.package,
+            &tool.module,
+            &tool.version,
+            &home,
+            writer,
+        )
+        .await?;
+    }
+
+    let pin_file = root.join(".golta.json");
+    let mut contents = if pin_file.exists() {
+        fs::read_to_string(&pin_file)?
+    } else {
+        String::new()
+    };
+    for tool in &declared {
+        contents = set_pinned_version(Some(&contents), &tool.name, &tool.version)?;
+    }
+    fs::write(&pin_file, contents)?;
+
+    writeln!(
+        writer,
+        "Pinned {} tool(s) to {}",
+        declared.len(),
+        pin_file.display()
+    )?;
+
+    Ok(())
+}
+
+fn read_tools_go_files(root: &Path) -> Result<Vec<String>, Box<dyn Error>> {
+    let mut sources = Vec::new();
+    for candidate in TOOLS_GO_CANDIDATES {
+        let path = root.join(candidate);
+        if path.is_file() {
+            sources.push(fs::read_to_string(path)?);
+        }
+    }
+    Ok(sources)
+}
+
+/// Collects tool packages from go.mod `tool` directives and tools.go blank imports,
+/// resolving each version from the `require` lines.
+fn declared_tools(
+    go_mod: &str,
+    tools_go_sources: &[String],
+) -> Result<Vec<DeclaredTool>, Box<dyn Error>> {
+    let parsed = parse_go_mod(go_mod);
+
+    let mut packages: Vec<String> = parsed.tools.clone();
+    for source in tools_go_sources {
+        packages.extend(parse_blank_imports(source));
+    }
+
+    let mut declared: Vec<DeclaredTool> = Vec::new();
+    for package in packages {
+        if declared.iter().any(|t| t.package == package) {
+            continue;
+        }
+        let requirement = parsed.version_for_package(&package).ok_or(format!(
+            "No `require` line in go.mod provides the tool package {}.",
+            package
+        ))?;
+        declared.push(DeclaredTool {
+            name: tool_name_from_package(&package),
+            package,
+            module: requirement.path.clone(),
+            version: requirement.version.clone(),
+        });
+    }
+
+    Ok(declared)
+}
+
+/// Returns the import paths of blank (`_`) imports in a Go source file.
+fn parse_blank_imports(source: &str) -> Vec<String> {
+    let mut imports = Vec::new();
+    let mut in_block = false;
+
+    for line in source.lines() {
+        let line = line.split("//").next().unwrap_or("").trim();
+        if in_block {
+            if line == ")" {
+                in_block = false;
+            } else if let Some(path) = blank_import_path(line) {
+                imports.push(path);
+            }
+        } else if line == "import (" {
+            in_block = true;
+        } else if let Some(spec) = line.strip_prefix("import ") {
+            if let Some(path) = blank_import_path(spec.trim()) {
+                imports.push(path);
+            }
+        }
+    }
+
+    imports
+}
+
+fn blank_import_path(spec: &str) -> Option<String> {
+    let path = spec.strip_prefix('_')?.trim().trim_matches('"');
+    if path.is_empty() {
+        None
+    } else {
+        Some(path.to_string())
+    }
+}
+
+/// Derives the binary name `go install` produces, skipping a trailing major version suffix.
+fn tool_name_from_package(package: &str) -> String {
+    let mut segments = package.rsplit('/');
+    let last = segments.next().unwrap_or(package);
+    let is_major_suffix =
+        last.len() > 1 && last.starts_with('v') && last[1..].chars().all(|c| c.is_ascii_digit());
+
+    if is_major_suffix {
+        segments.next().unwrap_or(last).to_string()
+    } else {
+        last.to_string()
+    }
+}
+
+/// Rebuilds installed tools that were not built with `target_go` (default: the active Go).
+/// When `built_with` is set, only tools built with that Go version are considered.
+pub(crate) fn rebuild_tools(
+    target_go: Option<&str>,
+    built_with: Option<&str>,
+    writer: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    let home = home::home_dir().ok_or("Could not find home directory")?;
+    let target = match target_go {
+        Some(version) => version.to_string(),
+        None => find_active_go_version()?.ok_or(
+            "No Go version is active. Use `--go <version>`, `golta pin` or `golta default`.",
+        )?,
+    };
+    let build_go = target_build_go(&home, &target, || BuildGo::system(&home))?;
+    // Tools record the version of the Go that built them, so compare against that.
+    let target = build_go
+        .version
+        .as_deref()
+        .unwrap_or(target.trim_start_matches("go@"));
+
+    let tools = list_installed_tools(&home.join(".golta").join("versions"))?;
+    let outdated: Vec<&InstalledTool> = outdated_tools(&tools, target)
+        .into_iter()
+        .filter(|t| built_with.is_none() || built_go(t) == built_with)
+        .collect();
+
+    if outdated.is_empty() {
+        writeln!(
+            writer,
+            "All installed tools are already built with Go {}.",
+            target
+        )?;
+        return Ok(());
+    }
+
+    for tool in outdated {
+        let package = match tool.build_info.as_ref().map(|info| info.package.as_str()) {
+            Some(package) => package.to_string(),
+            None => match get_tool_info(&tool.name) {
+                Some((package, _)) => package.to_string(),
+                None => {
+                    writeln!(
+                        writer,
+                        "Warning: skipping {} {}; its package path is unknown. Reinstall it to record one.",
+                        tool.name, tool.version
+                    )?;
+                    continue;
+                }
+            },
+        };
+
+        rebuild_tool(tool, &package, &build_go)?;
+        writeln!(
+            writer,
+            "Rebuilt {} {} with Go {} (previously {}).",
+            tool.name,
+            tool.version,
+            target,
+            built_go(tool).unwrap_or("unknown")
+        )?;
+    }
+
+    Ok(())
+}
+
+/// The toolchain to rebuild with: the system Go for `system`, otherwise the installed
+/// golta-managed `target`.
+fn target_build_go(
+    home: &Path,
+    target: &str,
+    system_go: impl FnOnce() -> Option<BuildGo>,
+) -> Result<BuildGo, Box<dyn Error>> {
+    if is_system_version(target) {
+        return system_go().ok_or_else(|| "No system `go` was found on PATH.".into());
+    }
+    let target = target.trim_start_matches("go@");
+    BuildGo::installed(home, target).ok_or_else(|| {
+        format!(
+            "Go {} is not installed. Please install it first with `golta install go@{}`.",
+            target, target
+        )
+        .into()
+    })
+}
+
+/// Builds into a staging directory first so a failed build leaves the old binary in place.
+fn rebuild_tool(
+    tool: &InstalledTool,
+    package: &str,
+    build_go: &BuildGo,
+) -> Result<(), Box<dyn Error>> {
+    let bin_dir = tool.install_dir.join("bin");
+    let staging_dir = tool.install_dir.join("bin.rebuild");
+    if staging_dir.exists() {
+        fs::remove_dir_all(&staging_dir)?;
+    }
+
+    if let Err(e) = build_tool(&tool.name, package, &tool.version, &staging_dir, build_go) {
+        fs::remove_dir_all(&staging_dir).ok();
+        return Err(e);
+    }
+
+    fs::remove_dir_all(&bin_dir)?;
+    fs::rename(&staging_dir, &bin_dir)?;
+    write_build_info(
+        &tool.install_dir,
+        &ToolBuildInfo {
+            package: package.to_string(),
+            go: build_go.version.clone(),
+        },
+    )
+}
+
+fn built_go(tool: &InstalledTool) -> Option<&str> {
+    tool.build_info.as_ref().and_then(|info| info.go.as_deref())
+}
+
+/// Returns the tools not known to be built with `target_go`.
+fn outdated_tools<'a>(tools: &'a [InstalledTool], target_go: &str) -> Vec<&'a InstalledTool> {
+    tools
+        .iter()
+        .filter(|t| built_go(t) != Some(target_go))
+        .collect()
+}
+
+/// `ToolState` abstracts how installed and pinned tool versions are looked up.
+trait ToolState {
+    fn installed_versions(&self, tool: &str) -> Result<Vec<String>, Box<dyn Error>>;
+    fn pinned_version(&self, tool: &str) -> Result<Option<String>, Box<dyn Error>>;
+}
+
+/// Looks up pins starting at the module `root`, not the current directory.
+struct FsToolState {
+    root: PathBuf,
+}
+
+impl ToolState for FsToolState {
+    fn installed_versions(&self, tool: &str) -> Result<Vec<String>, Box<dyn Error>> {
+        get_installed_tool_versions(tool)
+    }
+
+    fn pinned_version(&self, tool: &str) -> Result<Option<String>, Box<dyn Error>> {
+        Ok(find_pinned_tool_version_in(&self.root, tool)?.map(|(v, _)| v))
+    }
+}
+
+/// Describes every way the installed and pinned tools differ from the declarations.
+fn find_drift(
+    declared: &[DeclaredTool],
+    state: &impl ToolState,
+) -> Result<Vec<String>, Box<dyn Error>> {
+    let mut drift = Vec::new();
+
+    for tool in declared {
+        let installed = state.installed_versions(&tool.name)?;
+        if !installed.iter().any(|v| v == &tool.version) {
+            drift.push(format!(
+                "{} {} is declared but not installed",
+                tool.name, tool.version
+            ));
+        }
+
+        match state.pinned_version(&tool.name)? {
+            Some(pinned) if pinned == tool.version => {}
+            Some(pinned) => drift.push(format!(
+                "{} is pinned to {} but declared as {}",
+                tool.name, pinned, tool.version
+            )),
+            None => drift.push(format!("{} is not pinned in .golta.json", tool.name)),
+        }
+    }
+
+    Ok(drift)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    const GO_MOD: &str = "module example.com/app\n\ngo 1.24\n\nrequire (\n\tgolang.org/x/tools/gopls v0.18.1\n\tgithub.com/golangci/golangci-lint v1.64.5\n)\n\ntool golang.org/x/tools/gopls\n";
+
+    const TOOLS_GO: &str = r#"//go:build tools
+
+package tools
+
+import (
+	_ "github.com/golangci/golangci-lint/cmd/golangci-lint"
+	_ "golang.org/x/tools/gopls" // also a tool directive
+)
+"#;
+
+    #[derive(Default)]
+    struct MockState {
+        installed: HashMap<String, Vec<String>>,
+        pinned: HashMap<String, String>,
+    }
+
+    impl ToolState for MockState {
+        fn installed_versions(&self, tool: &str) -> Result<Vec<String>, Box<dyn Error>> {
+            Ok(self.installed.get(tool).cloned().unwrap_or_default())
+        }
+
+        fn pinned_version(&self, tool: &str) -> Result<Option<String>, Box<dyn Error>> {
+            Ok(self.pinned.get(tool).cloned())
+        }
+    }
+
+    #[test]
+    fn collects_tools_from_go_mod_and_tools_go() {
+        let declared = declared_tools(GO_MOD, &[TOOLS_GO.to_string()]).unwrap();
+
+        assert_eq!(
+            declared,
+            vec![
+                DeclaredTool {
+                    name: "gopls".to_string(),
+                    package: "golang.org/x/tools/gopls".to_string(),
+                    module: "golang.org/x/tools/gopls".to_string(),
+                    version: "v0.18.1".to_string(),
+                },
+                DeclaredTool {
+                    name: "golangci-lint".to_string(),
+                    package: "github.com/golangci/golangci-lint/cmd/golangci-lint".to_string(),
+                    module: "github.com/golangci/golangci-lint".to_string(),
+                    version: "v1.64.5".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn errors_when_tool_has_no_require_line() {
+        let err = declared_tools("module m\n\ntool example.com/cmd/thing\n", &[]).unwrap_err();
+
+        assert!(err.to_string().contains("example.com/cmd/thing"));
+    }
+
+    #[test]
+    fn parses_single_blank_import() {
+        let imports = parse_blank_imports(
+            "package tools\n\nimport _ \"honnef.co/go/tools/cmd/staticcheck\"\nimport \"fmt\"\n",
+        );
+
+        assert_eq!(imports, vec!["honnef.co/go/tools/cmd/staticcheck"]);
+    }
+
+    #[test]
+    fn tool_name_skips_major_version_suffix() {
+        assert_eq!(tool_name_from_package("golang.org/x/tools/gopls"), "gopls");
+        assert_eq!(
+            tool_name_from_package("github.com/golangci/golangci-lint/v2/cmd/golangci-lint"),
+            "golangci-lint"
+        );
+        assert_eq!(tool_name_from_package("github.com/air-verse/air/v2"), "air");
+    }
+
+    fn installed(name: &str, go: Option<&str>) -> InstalledTool {
+        InstalledTool {
+            name: name.to_string(),
+            version: "v1.0.0".to_string(),
+            install_dir: std::path::PathBuf::from("/tmp").join(name),
+            build_info: go.map(|go| ToolBuildInfo {
+                package: format!("example.com/{}", name),
+                go: Some(go.to_string()),
+            }),
+        }
+    }
+
+    #[test]
+    fn outdated_tools_include_unknown_and_other_go_versions() {
+        let tools = vec![
+            installed("gopls", Some("1.22.5")),
+            installed("dlv", Some("1.22.3")),
+            installed("air", None),
+        ];
+
+        let names: Vec<&str> = outdated_tools(&tools, "1.22.5")
+            .iter()
+            .map(|t| t.name.as_str())
+            .collect();
+
+        assert_eq!(names, vec!["dlv", "air"]);
+    }
+
+    #[test]
+    fn reports_no_drift_when_installed_and_pinned() {
+        let declared = declared_tools(GO_MOD, &[]).unwrap();
+        let state = MockState {
+            installed: HashMap::from([("gopls".to_string(), vec!["v0.18.1".to_string()])]),
+            pinned: HashMap::from([("gopls".to_string(), "v0.18.1".to_string())]),
+        };
+
+        assert!(find_drift(&declared, &state).unwrap().is_empty());
+    }
+
+    #[test]
+    fn reports_drift_for_missing_install_and_stale_pin() {
+        let declared = declared_tools(GO_MOD, &[]).unwrap();
+        let state = MockState {
+            installed: HashMap::from([("gopls".to_string(), vec!["v0.17.0".to_string()])]),
+            pinned: HashMap::from([("gopls".to_string(), "v0.17.0".to_string())]),
+        };
+
+        let drift = find_drift(&declared, &state).unwrap();
+
+        assert_eq!(
+            drift,
+            vec![
+                "gopls v0.18.1 is declared but not installed".to_string(),
+                "gopls is pinned to v0.17.0 but declared as v0.18.1".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_pins_from_the_module_root() {
+        let root = tempfile::tempdir().unwrap();
+        let sub = root.path().join("cmd");
+        fs::create_dir_all(&sub).unwrap();
+        fs::write(
+            root.path().join(".golta.json"),
+            r#"{"tools":{"gopls":"v0.18.1"}}"#,
+        )
+        .unwrap();
+        fs::write(sub.join(".golta.json"), r#"{"tools":{"gopls":"v0.17.0"}}"#).unwrap();
+        let state = FsToolState {
+            root: root.path().to_path_buf(),
+        };
+
+        assert_eq!(
+            state.pinned_version("gopls").unwrap(),
+            Some("v0.18.1".to_string())
+        );
+    }
+
+    #[test]
+    fn system_target_builds_with_the_system_go() {
+        let home = tempfile::tempdir().unwrap();
+        let bin = home.path().join(".golta/versions/1.22.3/go/bin");
+        fs::create_dir_all(&bin).unwrap();
+        fs::write(bin.join(if cfg!(windows) { "go.exe" } else { "go" }), "").unwrap();
+
+        let build_go = target_build_go(home.path(), "system", || {
+            BuildGo::installed(home.path(), "1.22.3")
+        })
+        .unwrap();
+        assert_eq!(build_go.version.as_deref(), Some("1.22.3"));
+
+        let err = target_build_go(home.path(), "system", || None).unwrap_err();
+        assert_eq!(err.to_string(), "No system `go` was found on PATH.");
+        let err = target_build_go(home.path(), "go@1.21.0", || None).unwrap_err();
+        assert!(err.to_string().starts_with("Go 1.21.0 is not installed."));
+    }
+}
@@ -1,8 +1,13 @@
-use crate::shared::pinned_version::find_pinned_go_version;
+use crate::cli::link::linked_go_root;
+use crate::cli::tools::rebuild_tools;
+use crate::shared::active_version::find_active_go_version;
+use crate::shared::local_versions::get_installed_versions;
+use crate::shared::tool_builds::{list_installed_tools, tools_built_with};
+use golta_core::pinned_version::find_pinned_go_version;
 use indicatif::{ProgressBar, ProgressStyle};
 use std::error::Error;
 use std::fs;
-use std::io::{self, Write};
+use std::io::{self, IsTerminal, Write};
 use std::path::{Path, PathBuf};
 
 pub fn run(tool_arg: String) {
@@ -17,7 +22,81 @@ pub fn run(tool_arg: String) {
 
     if let Err(e) = uninstall_tool(&tool_arg, &home, find_pinned_go_version, &mut stdout) {
         eprintln!("Error: {}", e);
+        return;
     }
+
+    if let Ok((tool, version)) = parse_tool_version(&tool_arg) {
+        if tool == "go" {
+            if let Err(e) = offer_tool_rebuild(&version, &home, &mut stdout) {
+                eprintln!("Error: {}", e);
+            }
+        }
+    }
+}
+
+/// Lists tools that were built with the removed Go version and offers to rebuild them.
+fn offer_tool_rebuild<W: Write>(
+    version: &str,
+    home: &Path,
+    writer: &mut W,
+) -> Result<(), Box<dyn Error>> {
+    let tools = list_installed_tools(&home.join(".golta").join("versions"))?;
+    let affected = tools_built_with(&tools, version);
+    if affected.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(
+        writer,
+        "The following tools were built with Go {}:",
+        version
+    )?;
+    for tool in &affected {
+        writeln!(writer, "  {} {}", tool.name, tool.version)?;
+    }
+
+    // The active version is often the one just removed (it was pinned or the default),
+    // so only offer a rebuild with a Go that is still installed.
+    let active = find_active_go_version().ok().flatten();
+    let installed = get_installed_versions().unwrap_or_default();
+    let Some(target) = rebuild_target(active.as_deref(), version, &installed) else {
+        writeln!(
+            writer,
+            "Run `golta tools rebuild --go <version>` to rebuild them with an installed Go version."
+        )?;
+        return Ok(());
+    };
+
+    if !io::stdin().is_terminal() {
+        writeln!(
+            writer,
+            "Run `golta tools rebuild --go {}` to rebuild them.",
+            target
+        )?;
+        return Ok(());
+    }
+
+    write!(writer, "Rebuild them with Go {} now? [y/N] ", target)?;
+    writer.flush()?;
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    let input = input.trim().to_lowercase();
+    if input == "y" || input == "yes" {
+        rebuild_tools(Some(target), Some(version), writer)?;
+    }
+
+    Ok(())
+}
+
+/// The Go version to rebuild with: the active one, unless it is the removed `version` or
+/// is not installed.
+fn rebuild_target<'a>(
+    active: Option<&'a str>,
+    removed: &str,
+    installed: &[String],
+) -> Option<&'a str> {
+    let active = active?.trim_start_matches("go@");
+    (active != removed && installed.iter().any(|v| v == active)).then_some(active)
 }
 
 fn uninstall_tool<W, F>(
@@ -41,6 +120,19 @@ where
 
     if tool == "go" {
         warn_if_pinned(&version, find_pinned, writer)?;
+
+        // A version added with `golta link` is only unregistered; its files are not ours.
+        if let Some(go_root) = linked_go_root(&version_dir) {
+            remove_dir_link(&version_dir.join("go"))?;
+            fs::remove_dir_all(&version_dir)?;
+            writeln!(
+                writer,
+                "go@{} has been unlinked; {} was left in place.",
+                version,
+                go_root.display()
+            )?;
+            return Ok(());
+        }
     }
 
     let pb = ProgressBar::new_spinner();
@@ -50,11 +142,24 @@ where
 
     fs::remove_dir_all(&version_dir)?;
     pb.finish_and_clear();
-    writeln!(writer, "{} {} has been uninstalled.", tool, version)?;
+    let display_name = if tool == "go" { "Go" } else { tool.as_str() };
+    writeln!(writer, "{} {} has been uninstalled.", display_name, version)?;
 
     Ok(())
 }
 
+#[cfg(unix)]
+fn remove_dir_link(link: &Path) -> Result<(), Box<dyn Error>> {
+    fs::remove_file(link)?;
+    Ok(())
+}
+
+#[cfg(windows)]
+fn remove_dir_link(link: &Path) -> Result<(), Box<dyn Error>> {
+    fs::remove_dir(link)?;
+    Ok(())
+}
+
 fn parse_tool_version(input: &str) -> Result<(String, String), Box<dyn Error>> {
     if let Some((tool, version)) = input.split_once('@') {
         if !tool.is_empty() && !version.is_empty() {
@@ -173,6 +278,40 @@ mod tests {
         fs::remove_dir_all(home).unwrap();
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn only_unlinks_linked_versions() {
+        let home = temp_home();
+        let external = home.join("sdk").join("go1.21.0");
+        fs::create_dir_all(external.join("bin")).unwrap();
+        fs::write(external.join("bin").join("go"), "").unwrap();
+        let (version_dir, _) = build_paths(&home, "go", "1.21.0");
+        fs::create_dir_all(&version_dir).unwrap();
+        std::os::unix::fs::symlink(&external, version_dir.join("go")).unwrap();
+
+        let mut buffer = Vec::new();
+        uninstall_tool("go@1.21.0", &home, || Ok(None), &mut buffer).unwrap();
+
+        assert!(!version_dir.exists());
+        assert!(external.join("bin").join("go").exists());
+        let output = String::from_utf8(buffer).unwrap();
+        assert!(output.contains("go@1.21.0 has been unlinked"));
+        fs::remove_dir_all(home).unwrap();
+    }
+
+    #[test]
+    fn rebuilds_only_with_a_remaining_active_version() {
+        let installed = vec!["1.22.5".to_string()];
+
+        assert_eq!(
+            rebuild_target(Some("1.22.5"), "1.21.0", &installed),
+            Some("1.22.5")
+        );
+        assert_eq!(rebuild_target(Some("1.21.0"), "1.21.0", &installed), None);
+        assert_eq!(rebuild_target(Some("1.23.0"), "1.21.0", &installed), None);
+        assert_eq!(rebuild_target(None, "1.21.0", &installed), None);
+    }
+
     #[test]
     fn uninstalls_other_tool() {
         let home = temp_home();
@@ -1,105 +1,239 @@
-﻿use std::env;
+use golta_core::pinned_version::{
+    self, find_pin_file_from, find_pinned_go_version_from, remove_pinned_version,
+};
+use golta_core::project_file::PROJECT_FILE_NAME;
+use std::env;
 use std::error::Error;
 use std::fs;
 use std::path::{Path, PathBuf};
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum UnpinOutcome {
-    Removed,
+    /// The pin was removed and the file still holds other settings.
+    Removed(PathBuf),
+    /// The pin was the last setting, so the file was deleted.
+    RemovedFile(PathBuf),
+    /// The version comes from a file golta does not manage, such as `.go-version` or go.mod.
+    PinnedElsewhere(PathBuf),
     NotPinned,
 }
 
-pub trait PinFileSystem {
-    fn exists(&self, path: &Path) -> bool;
+pub trait PinFileSystem: pinned_version::PinFileSystem {
+    fn write(&self, path: &Path, contents: &str) -> Result<(), Box<dyn Error>>;
     fn remove_file(&self, path: &Path) -> Result<(), Box<dyn Error>>;
 }
 
 struct StdFs;
 
-impl PinFileSystem for StdFs {
+impl pinned_version::PinFileSystem for StdFs {
     fn exists(&self, path: &Path) -> bool {
         path.exists()
     }
 
+    fn read_to_string(&self, path: &Path) -> Result<String, Box<dyn Error>> {
+        Ok(fs::read_to_string(path)?)
+    }
+}
+
+impl PinFileSystem for StdFs {
+    fn write(&self, path: &Path, contents: &str) -> Result<(), Box<dyn Error>> {
+        fs::write(path, contents)?;
+        Ok(())
+    }
+
     fn remove_file(&self, path: &Path) -> Result<(), Box<dyn Error>> {
         fs::remove_file(path)?;
         Ok(())
     }
 }
 
-pub fn run() {
+pub fn run(tool: Option<String>) {
     let fs = StdFs;
-    match current_pin_file().and_then(|pin_file| unpin(&fs, pin_file.as_path())) {
-        Ok(UnpinOutcome::Removed) => println!("Removed pinned version for this project."),
-        Ok(UnpinOutcome::NotPinned) => println!("No Go version is pinned in this directory."),
+    let tool = tool.unwrap_or_else(|| "go".to_string());
+    let tool = tool.split_once('@').map_or(tool.as_str(), |(name, _)| name);
+    match env::current_dir()
+        .map_err(Into::into)
+        .and_then(|dir| unpin(&fs, &dir, tool))
+    {
+        Ok(UnpinOutcome::Removed(path)) => {
+            println!("Removed the {} pin from {}", tool, path.display())
+        }
+        Ok(UnpinOutcome::RemovedFile(path)) => println!(
+            "Removed the {} pin and the now empty {}",
+            tool,
+            path.display()
+        ),
+        Ok(UnpinOutcome::PinnedElsewhere(path)) => println!(
+            "The {} version is set by {}, not by a {}; edit that file to change it.",
+            tool,
+            path.display(),
+            PROJECT_FILE_NAME
+        ),
+        Ok(UnpinOutcome::NotPinned) => println!(
+            "No {} version is pinned in this directory or its parents.",
+            tool
+        ),
         Err(e) => eprintln!("Error: {}", e),
     }
 }
 
-fn current_pin_file() -> Result<PathBuf, Box<dyn Error>> {
-    Ok(env::current_dir()?.join(".golta.json"))
-}
-
-pub fn unpin<F: PinFileSystem>(fs: &F, pin_file: &Path) -> Result<UnpinOutcome, Box<dyn Error>> {
-    if fs.exists(pin_file) {
-        fs.remove_file(pin_file)?;
-        return Ok(UnpinOutcome::Removed);
+/// Removes `tool` from the nearest `.golta.json` that pins it, searching upward from
+/// `start_dir` like the shim does, and deletes the file once nothing else is left in it.
+/// For Go, the file that actually decides the version is found with the shim's
+/// resolution; when that is `.go-version`, `.tool-versions` or go.mod it is reported
+/// and nothing is edited.
+pub fn unpin<F: PinFileSystem>(
+    fs: &F,
+    start_dir: &Path,
+    tool: &str,
+) -> Result<UnpinOutcome, Box<dyn Error>> {
+    let pin_file = if tool == "go" {
+        match find_pinned_go_version_from(fs, start_dir)? {
+            Some((_, path))
+                if path
+                    .file_name()
+                    .is_some_and(|name| name == PROJECT_FILE_NAME) =>
+            {
+                Some(path)
+            }
+            Some((_, path)) => return Ok(UnpinOutcome::PinnedElsewhere(path)),
+            None => None,
+        }
+    } else {
+        find_pin_file_from(fs, start_dir, tool)?
+    };
+    let Some(pin_file) = pin_file else {
+        return Ok(UnpinOutcome::NotPinned);
+    };
+
+    let content = fs.read_to_string(&pin_file)?;
+    match remove_pinned_version(&content, tool)? {
+        Some(remaining) => {
+            fs.write(&pin_file, &remaining)?;
+            Ok(UnpinOutcome::Removed(pin_file))
+        }
+        None => {
+            fs.remove_file(&pin_file)?;
+            Ok(UnpinOutcome::RemovedFile(pin_file))
+        }
     }
-
-    Ok(UnpinOutcome::NotPinned)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::cell::RefCell;
+    use std::collections::HashMap;
 
     struct MockFs {
-        files: RefCell<Vec<PathBuf>>,
+        files: RefCell<HashMap<PathBuf, String>>,
     }
 
     impl MockFs {
-        fn new(files: Vec<PathBuf>) -> Self {
+        fn new(files: Vec<(&str, &str)>) -> Self {
             Self {
-                files: RefCell::new(files),
+                files: RefCell::new(
+                    files
+                        .into_iter()
+                        .map(|(path, content)| (PathBuf::from(path), content.to_string()))
+                        .collect(),
+                ),
             }
         }
+
+        fn content(&self, path: &str) -> Option<String> {
+            self.files.borrow().get(Path::new(path)).cloned()
+        }
     }
 
-    impl PinFileSystem for MockFs {
+    impl pinned_version::PinFileSystem for MockFs {
         fn exists(&self, path: &Path) -> bool {
-            self.files.borrow().iter().any(|p| p == path)
+            self.files.borrow().contains_key(path)
+        }
+
+        fn read_to_string(&self, path: &Path) -> Result<String, Box<dyn Error>> {
+            self.files
+                .borrow()
+                .get(path)
+                .cloned()
+                .ok_or_else(|| "file missing".into())
+        }
+    }
+
+    impl PinFileSystem for MockFs {
+        fn write(&self, path: &Path, contents: &str) -> Result<(), Box<dyn Error>> {
+            self.files
+                .borrow_mut()
+                .insert(path.to_path_buf(), contents.to_string());
+            Ok(())
         }
 
         fn remove_file(&self, path: &Path) -> Result<(), Box<dyn Error>> {
-            let mut files = self.files.borrow_mut();
-            if let Some(pos) = files.iter().position(|p| p == path) {
-                files.remove(pos);
-                Ok(())
-            } else {
-                Err("file missing".into())
-            }
+            self.files
+                .borrow_mut()
+                .remove(path)
+                .map(|_| ())
+                .ok_or_else(|| "file missing".into())
         }
     }
 
     #[test]
-    fn removes_when_present() {
-        let pin = PathBuf::from(".golta.json");
-        let fs = MockFs::new(vec![pin.clone()]);
+    fn removes_file_when_pin_was_its_only_key() {
+        let fs = MockFs::new(vec![("project/.golta.json", r#"{"go":"1.22.3"}"#)]);
+
+        let result = unpin(&fs, Path::new("project"), "go").unwrap();
+
+        assert_eq!(
+            result,
+            UnpinOutcome::RemovedFile(PathBuf::from("project/.golta.json"))
+        );
+        assert_eq!(fs.content("project/.golta.json"), None);
+    }
 
-        let result = unpin(&fs, &pin).unwrap();
+    #[test]
+    fn edits_pin_file_found_in_a_parent() {
+        let fs = MockFs::new(vec![(
+            "project/.golta.json",
+            r#"{"go":"1.22.3","tools":{"gopls":"v0.16.1"}}"#,
+        )]);
+
+        let result = unpin(&fs, Path::new("project/cmd/app"), "gopls").unwrap();
+
+        assert_eq!(
+            result,
+            UnpinOutcome::Removed(PathBuf::from("project/.golta.json"))
+        );
+        let content = fs.content("project/.golta.json").unwrap();
+        assert!(content.contains("\"go\": \"1.22.3\""));
+        assert!(!content.contains("gopls"));
+    }
 
-        assert_eq!(result, UnpinOutcome::Removed);
-        assert!(!fs.exists(&pin));
+    #[test]
+    fn reports_nearer_go_version_file_without_editing() {
+        let fs = MockFs::new(vec![
+            ("project/.golta.json", r#"{"go":"1.22.3"}"#),
+            ("project/app/.go-version", "1.21.0\n"),
+        ]);
+
+        let result = unpin(&fs, Path::new("project/app"), "go").unwrap();
+
+        assert_eq!(
+            result,
+            UnpinOutcome::PinnedElsewhere(PathBuf::from("project/app/.go-version"))
+        );
+        assert_eq!(
+            fs.content("project/.golta.json").as_deref(),
+            Some(r#"{"go":"1.22.3"}"#)
+        );
     }
 
     #[test]
     fn reports_not_pinned_when_absent() {
-        let pin = PathBuf::from(".golta.json");
-        let fs = MockFs::new(vec![]);
+        let fs = MockFs::new(vec![("project/.golta.json", r#"{"go":"1.22.3"}"#)]);
 
-        let result = unpin(&fs, &pin).unwrap();
+        let result = unpin(&fs, Path::new("project"), "dlv").unwrap();
 
         assert_eq!(result, UnpinOutcome::NotPinned);
+        assert!(fs.content("project/.golta.json").is_some());
     }
 }
@@ -0,0 +1,185 @@
+use crate::shared::local_versions::{get_installed_tool_versions, get_installed_versions};
+use clap::ValueEnum;
+use golta_core::resolve::session_override_var;
+use golta_core::system::is_system_version;
+use std::error::Error;
+use std::io::Write;
+
+/// The shell dialect `golta use` prints its statements in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum UseShell {
+    Sh,
+    Fish,
+    Powershell,
+}
+
+pub fn run(tool: String, unset: bool, shell: Option<UseShell>) {
+    let mut out = std::io::stdout();
+    if let Err(e) = use_version(&tool, unset, shell, &FsInstalled, &mut out) {
+        eprintln!("Error: {}", e);
+    }
+}
+
+/// Prints the statement that sets (or clears) the session override for a tool.
+/// The shell function written by `golta setup` evaluates it in the current shell.
+fn use_version(
+    tool_arg: &str,
+    unset: bool,
+    shell: Option<UseShell>,
+    installed: &impl InstalledVersions,
+    out: &mut dyn Write,
+) -> Result<(), Box<dyn Error>> {
+    let (tool, version) = if unset {
+        let tool = tool_arg.split('@').next().unwrap_or(tool_arg);
+        (tool, None)
+    } else {
+        let (tool, version) = tool_arg
+            .split_once('@')
+            .ok_or("Invalid format. Use <tool>@<version>, or --unset <tool>.")?;
+        (tool, Some(version))
+    };
+    if tool.is_empty() {
+        return Err("Tool name must not be empty.".into());
+    }
+    if !is_safe_tool_name(tool) {
+        return Err(format!("Invalid tool name '{}'.", tool).into());
+    }
+
+    if let Some(version) = version {
+        if !is_safe_version(version) {
+            return Err(format!("Invalid version '{}'.", version).into());
+        }
+        let is_system_go = tool == "go" && is_system_version(version);
+        if !is_system_go && !installed.versions(tool)?.iter().any(|v| v == version) {
+            return Err(format!(
+                "{}@{} is not installed. Run `golta install {}@{}` first.",
+                tool, version, tool, version
+            )
+            .into());
+        }
+    }
+
+    let statement = shell_statement(
+        shell.unwrap_or(UseShell::Sh),
+        &session_override_var(tool),
+        version,
+    );
+    writeln!(out, "{}", statement)?;
+
+    if shell.is_none() {
+        eprintln!("Shell integration is not active, so the current shell was not changed.");
+        eprintln!("Run `golta setup` to install it, or run the line above yourself.");
+    }
+    Ok(())
+}
+
+/// Builds the statement that exports (or removes) `var` in the given shell.
+fn shell_statement(shell: UseShell, var: &str, version: Option<&str>) -> String {
+    match (shell, version) {
+        (UseShell::Sh, Some(v)) => format!("export {}='{}'", var, v),
+        (UseShell::Sh, None) => format!("unset {}", var),
+        (UseShell::Fish, Some(v)) => format!("set -gx {} '{}'", var, v),
+        (UseShell::Fish, None) => format!("set -e {}", var),
+        (UseShell::Powershell, Some(v)) => format!("$env:{} = '{}'", var, v),
+        (UseShell::Powershell, None) => {
+            format!("Remove-Item Env:{} -ErrorAction SilentlyContinue", var)
+        }
+    }
+}
+
+/// Versions are spliced into shell code, so only allow the characters versions use.
+fn is_safe_version(version: &str) -> bool {
+    !version.is_empty()
+        && version
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '+' | '_'))
+}
+
+/// Tool names become part of a variable name in shell code, so only allow the
+/// characters tool names use.
+fn is_safe_tool_name(tool: &str) -> bool {
+    tool.chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'))
+}
+
+trait InstalledVersions {
+    fn versions(&self, tool: &str) -> Result<Vec<String>, Box<dyn Error>>;
+}
+
+struct FsInstalled;
+
+impl InstalledVersions for FsInstalled {
+    fn versions(&self, tool: &str) -> Result<Vec<String>, Box<dyn Error>> {
+        if tool == "go" {
+            get_installed_versions()
+        } else {
+            get_installed_tool_versions(tool)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct MockInstalled(Vec<&'static str>);
+
+    impl InstalledVersions for MockInstalled {
+        fn versions(&self, _tool: &str) -> Result<Vec<String>, Box<dyn Error>> {
+            Ok(self.0.iter().map(|v| v.to_string()).collect())
+        }
+    }
+
+    fn run_use(arg: &str, unset: bool, shell: UseShell) -> Result<String, Box<dyn Error>> {
+        let installed = MockInstalled(vec!["1.21.0", "v0.16.1"]);
+        let mut out = Vec::new();
+        use_version(arg, unset, Some(shell), &installed, &mut out)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn exports_override_for_installed_version() {
+        assert_eq!(
+            run_use("go@1.21.0", false, UseShell::Sh).unwrap(),
+            "export GOLTA_GO_VERSION='1.21.0'\n"
+        );
+        assert_eq!(
+            run_use("gopls@v0.16.1", false, UseShell::Fish).unwrap(),
+            "set -gx GOLTA_GOPLS_VERSION 'v0.16.1'\n"
+        );
+    }
+
+    #[test]
+    fn unsets_override() {
+        assert_eq!(
+            run_use("go", true, UseShell::Sh).unwrap(),
+            "unset GOLTA_GO_VERSION\n"
+        );
+        assert_eq!(
+            run_use("go", true, UseShell::Powershell).unwrap(),
+            "Remove-Item Env:GOLTA_GO_VERSION -ErrorAction SilentlyContinue\n"
+        );
+    }
+
+    #[test]
+    fn rejects_missing_or_unsafe_versions() {
+        let err = run_use("go@1.22.0", false, UseShell::Sh).unwrap_err();
+        assert!(err.to_string().contains("golta install go@1.22.0"));
+
+        assert!(run_use("go@1.21.0';rm", false, UseShell::Sh).is_err());
+        assert!(run_use("go", false, UseShell::Sh).is_err());
+    }
+
+    #[test]
+    fn rejects_unsafe_tool_names() {
+        for shell in [UseShell::Sh, UseShell::Fish, UseShell::Powershell] {
+            let err = run_use("x;touch pwned", true, shell).unwrap_err();
+            assert_eq!(err.to_string(), "Invalid tool name 'x;touch pwned'.");
+            assert!(run_use("$(id)@1.21.0", false, shell).is_err());
+        }
+        assert_eq!(
+            run_use("golangci-lint", true, UseShell::Sh).unwrap(),
+            "unset GOLTA_GOLANGCI_LINT_VERSION\n"
+        );
+    }
+}
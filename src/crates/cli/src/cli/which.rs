@@ -1,4 +1,6 @@
 use crate::shared::active_version::find_active_go_version;
+use golta_core::system::{default_shim_path, find_system_program, is_system_version};
+use std::env;
 use std::error::Error;
 use std::path::{Path, PathBuf};
 
@@ -22,7 +24,27 @@ fn which_go(tool: &str) -> Result<PathBuf, Box<dyn Error>> {
     }
 
     let home = home::home_dir().ok_or("Could not find home directory")?;
-    Ok(resolve_go_path(&home, &version))
+    go_path(&home, &version, || {
+        find_system_program(
+            "go",
+            env::var_os("PATH").as_deref(),
+            &home,
+            &[default_shim_path(&home)],
+        )
+    })
+}
+
+/// The `go` binary for `version`: the system Go found on `PATH` for `system`, otherwise
+/// the toolchain under `~/.golta/versions`.
+fn go_path(
+    home: &Path,
+    version: &str,
+    system_go: impl FnOnce() -> Option<PathBuf>,
+) -> Result<PathBuf, Box<dyn Error>> {
+    if is_system_version(version) {
+        return system_go().ok_or_else(|| "No system `go` was found on PATH.".into());
+    }
+    Ok(resolve_go_path(home, version))
 }
 
 fn resolve_go_path(home: &Path, version: &str) -> PathBuf {
@@ -56,6 +78,19 @@ mod tests {
         assert_eq!(path, expected);
     }
 
+    #[test]
+    fn system_version_uses_go_from_path() {
+        let home = PathBuf::from("/tmp/home");
+
+        let path = go_path(&home, "system", || {
+            Some(PathBuf::from("/usr/local/go/bin/go"))
+        });
+        assert_eq!(path.unwrap(), PathBuf::from("/usr/local/go/bin/go"));
+
+        let err = go_path(&home, "go@system", || None).unwrap_err();
+        assert_eq!(err.to_string(), "No system `go` was found on PATH.");
+    }
+
     #[test]
     fn builds_go_path_without_prefix() {
         let home = PathBuf::from("/tmp/home");
@@ -1,12 +1,14 @@
 mod cli;
 mod shared;
 
-use clap::{Parser, Subcommand};
+use clap::{ArgAction, Parser, Subcommand};
 use clap_complete::Shell;
 use cli::{
-    completions, default, exec, install, list, list_remote, pin, run, setup, uninstall, unpin,
-    which,
+    completions, config, default, exec, import, install, link, list, list_remote, pin, run, setup,
+    status, tools, uninstall, unpin, use_version, which,
 };
+use golta_core::config::AutoInstall;
+use std::path::PathBuf;
 
 #[derive(Parser)]
 #[command(propagate_version = true)]
@@ -36,6 +38,27 @@ pub enum Commands {
         /// The tool and version to uninstall (e.g., "go@1.23.0")
         tool: String,
     },
+    #[command(
+        about = "Register a Go toolchain installed outside golta (e.g., go@1.22.3 /usr/lib/go)"
+    )]
+    Link {
+        /// The name to register it as (e.g., "go@1.22.3"), or "go" to use its VERSION file
+        tool: String,
+        /// The GOROOT of the toolchain (the directory containing bin/go and VERSION)
+        go_root: PathBuf,
+    },
+    #[command(about = "Import Go installations from another version manager")]
+    Import {
+        /// The version manager to import from
+        #[arg(long, value_enum)]
+        from: import::ImportSource,
+        /// How to bring the toolchains into golta
+        #[arg(long, value_enum, default_value_t = import::ImportMode::Copy)]
+        mode: import::ImportMode,
+        /// Only report what would be imported
+        #[arg(long)]
+        dry_run: bool,
+    },
     #[command(
         about = "Manage the global default version for a tool (alias: df)",
         alias = "df"
@@ -59,21 +82,56 @@ pub enum Commands {
         #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
         args: Vec<String>,
     },
-    #[command(about = "Pin a tool version to the current project (.golta.json)")]
+    #[command(
+        about = "Pin a tool version to the current project (.golta.json); `1.22` and `stable` follow new installs"
+    )]
     Pin {
         /// The tool and version to pin (e.g., "go@1.23.0")
         tool: String,
-        /// Automatically update the 'go' version in go.mod
-        #[arg(long, default_value_t = true)]
+        /// Update the `go` and `toolchain` lines of go.mod (or go.work) to match; pass
+        /// `--update-go-mod=false` to leave it alone
+        #[arg(
+            long,
+            default_value_t = true,
+            action = ArgAction::Set,
+            num_args = 0..=1,
+            require_equals = true,
+            default_missing_value = "true"
+        )]
         update_go_mod: bool,
+        /// Pin the installed version a spec such as `1.22` or `stable` currently selects
+        #[arg(long)]
+        exact: bool,
+        /// Install the newest release matching the version first
+        #[arg(long)]
+        install: bool,
+        /// Write .golta.json in the current directory instead of the module or repository root
+        #[arg(long)]
+        here: bool,
     },
     #[command(about = "Unpin the tool version from the current project")]
-    Unpin,
+    Unpin {
+        /// The tool to unpin (e.g., "gopls"); defaults to "go"
+        tool: Option<String>,
+    },
     #[command(about = "Display the full path to the currently active tool executable")]
     Which {
         /// The tool to find (e.g., "go")
         tool: String,
     },
+    #[command(about = "Show the active Go version and the file that selected it")]
+    Status,
+    #[command(about = "Use a tool version for the current shell session only")]
+    Use {
+        /// The tool and version to use (e.g., "go@1.21.0"), or just the tool with --unset
+        tool: String,
+        /// Clear the session override instead of setting it
+        #[arg(long)]
+        unset: bool,
+        /// Print the statement for this shell (used by the shell integration from `golta setup`)
+        #[arg(long, value_enum)]
+        shell: Option<use_version::UseShell>,
+    },
     #[command(about = "List all installed versions (alias: ls)", alias = "ls")]
     List {
         /// The tool to list versions for
@@ -87,6 +145,10 @@ pub enum Commands {
         /// The tool to list remote versions for
         tool: Option<String>,
     },
+    #[command(about = "Manage the Go tools declared by the project (go.mod and tools.go)")]
+    Tools(ToolsCommand),
+    #[command(about = "Check project configuration files")]
+    Config(ConfigCommand),
     #[command(about = "Generate shell completion scripts")]
     Completions {
         /// The shell to generate completions for
@@ -98,12 +160,19 @@ pub enum Commands {
 }
 
 #[derive(Parser)]
+#[command(subcommand_negates_reqs = true)]
 pub struct DefaultCommand {
     #[command(subcommand)]
     command: Option<DefaultCommands>,
-    /// The tool and version to set as default (e.g., "go@1.23.0")
-    #[arg(required_unless_present = "command")]
+    /// The tool and version to set as default (e.g., "go@1.23.0", "go@1.22" or "go@stable")
+    #[arg(required = true)]
     tool: Option<String>,
+    /// Store the installed version a spec such as `1.22` or `stable` currently selects
+    #[arg(long)]
+    exact: bool,
+    /// Install the newest release matching the version first
+    #[arg(long)]
+    install: bool,
 }
 
 #[derive(Subcommand)]
@@ -112,6 +181,55 @@ pub enum DefaultCommands {
     Clear,
 }
 
+#[derive(Parser)]
+pub struct ToolsCommand {
+    #[command(subcommand)]
+    command: ToolsCommands,
+}
+
+#[derive(Parser)]
+pub struct ConfigCommand {
+    #[command(subcommand)]
+    command: ConfigCommands,
+}
+
+#[derive(Subcommand)]
+pub enum ConfigCommands {
+    #[command(
+        about = "Validate .golta.json against its schema, reporting the line and column of errors"
+    )]
+    Validate {
+        /// The file to check (defaults to the .golta.json that applies to the current directory)
+        path: Option<PathBuf>,
+    },
+    #[command(
+        name = "auto-install",
+        about = "Show or set what the shim does when the selected Go version is not installed"
+    )]
+    AutoInstall {
+        /// never, prompt or always (shows the current setting when omitted)
+        policy: Option<AutoInstall>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ToolsCommands {
+    #[command(
+        about = "Install and pin the tools declared in go.mod `tool` directives and tools.go"
+    )]
+    Sync {
+        /// Only check that installed and pinned tools match the declarations
+        #[arg(long)]
+        check: bool,
+    },
+    #[command(about = "Rebuild installed tools that were built with a different Go version")]
+    Rebuild {
+        /// The Go version to rebuild with (defaults to the active version)
+        #[arg(long)]
+        go: Option<String>,
+    },
+}
+
 #[tokio::main]
 async fn main() {
     let cli = Cli::parse();
@@ -119,17 +237,41 @@ async fn main() {
     match cli.command {
         Commands::Install { tool } => install::run(tool).await,
         Commands::Uninstall { tool } => uninstall::run(tool),
-        Commands::Default(cmd) => default::run(cmd),
+        Commands::Link { tool, go_root } => link::run(tool, go_root),
+        Commands::Import {
+            from,
+            mode,
+            dry_run,
+        } => import::run(from, mode, dry_run),
+        Commands::Default(cmd) => default::run(cmd).await,
         Commands::Run { tool, args } => run::run(tool, args),
         Commands::Exec { tool, args } => exec::run(tool, args),
         Commands::Pin {
             tool,
             update_go_mod,
-        } => pin::run(tool, update_go_mod),
-        Commands::Unpin => unpin::run(),
+            exact,
+            install,
+            here,
+        } => {
+            pin::run(
+                tool,
+                pin::PinOptions {
+                    update_go_mod,
+                    exact,
+                    install,
+                    here,
+                },
+            )
+            .await
+        }
+        Commands::Unpin { tool } => unpin::run(tool),
         Commands::Which { tool } => which::run(tool),
+        Commands::Status => status::run(),
+        Commands::Use { tool, unset, shell } => use_version::run(tool, unset, shell),
         Commands::List { tool } => list::run(tool),
         Commands::ListRemote { tool } => list_remote::run(tool).await,
+        Commands::Tools(cmd) => tools::run(cmd).await,
+        Commands::Config(cmd) => config::run(cmd),
         Commands::Completions { shell } => completions::run(shell, &mut std::io::stdout()),
         Commands::Setup => setup::run(),
     }
@@ -1,13 +1,21 @@
+use golta_core::resolve::installed_go_versions;
 use std::error::Error;
 use std::fs;
 use std::path::Path;
 
 /// Returns a list of locally installed Go version strings.
-/// It reads the `~/.golta/versions` directory and returns the names of the subdirectories.
+/// It reads the `~/.golta/versions` directory, skipping the directories of Go tools.
 pub fn get_installed_versions() -> Result<Vec<String>, Box<dyn Error>> {
     let home = home::home_dir().ok_or("Could not find home directory")?;
-    let versions_dir = home.join(".golta").join("versions");
-    get_installed_versions_from_path(&versions_dir)
+    installed_go_versions(&home)
+}
+
+/// Returns a list of locally installed versions of a Go tool such as `gopls`.
+/// It reads the `~/.golta/versions/<tool>` directory and returns the names of the subdirectories.
+pub fn get_installed_tool_versions(tool: &str) -> Result<Vec<String>, Box<dyn Error>> {
+    let home = home::home_dir().ok_or("Could not find home directory")?;
+    let tool_dir = home.join(".golta").join("versions").join(tool);
+    get_installed_versions_from_path(&tool_dir)
 }
 
 /// Returns a list of Go version strings from a specific directory path.
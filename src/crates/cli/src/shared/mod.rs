@@ -1,5 +1,5 @@
 pub mod active_version;
 pub mod local_versions;
-pub mod os_info;
-pub mod pinned_version;
+pub mod tool_builds;
+pub mod tool_compat;
 pub mod versions;
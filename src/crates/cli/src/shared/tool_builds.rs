@@ -0,0 +1,138 @@
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// File written next to a tool's `bin` directory describing how it was built.
+const BUILD_INFO_FILE: &str = "golta-tool.json";
+
+/// Records which package a tool binary was built from and with which Go version.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ToolBuildInfo {
+    pub package: String,
+    pub go: Option<String>,
+}
+
+/// A tool version installed under `~/.golta/versions/<tool>/<version>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstalledTool {
+    pub name: String,
+    pub version: String,
+    pub install_dir: PathBuf,
+    pub build_info: Option<ToolBuildInfo>,
+}
+
+pub fn read_build_info(install_dir: &Path) -> Option<ToolBuildInfo> {
+    let content = fs::read_to_string(install_dir.join(BUILD_INFO_FILE)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+pub fn write_build_info(install_dir: &Path, info: &ToolBuildInfo) -> Result<(), Box<dyn Error>> {
+    let contents = serde_json::to_string_pretty(info)?;
+    fs::write(install_dir.join(BUILD_INFO_FILE), contents)?;
+    Ok(())
+}
+
+/// Lists every installed tool version. Go toolchains (directories holding `go/`) are skipped.
+pub fn list_installed_tools(versions_dir: &Path) -> Result<Vec<InstalledTool>, Box<dyn Error>> {
+    let mut tools = Vec::new();
+    if !versions_dir.exists() {
+        return Ok(tools);
+    }
+
+    for entry in fs::read_dir(versions_dir)? {
+        let entry = entry?;
+        let tool_dir = entry.path();
+        if !entry.file_type()?.is_dir() || tool_dir.join("go").exists() {
+            continue;
+        }
+        let Some(name) = entry.file_name().to_str().map(str::to_string) else {
+            continue;
+        };
+
+        for version_entry in fs::read_dir(&tool_dir)? {
+            let version_entry = version_entry?;
+            let install_dir = version_entry.path();
+            if !install_dir.join("bin").is_dir() {
+                continue;
+            }
+            if let Some(version) = version_entry.file_name().to_str() {
+                tools.push(InstalledTool {
+                    name: name.clone(),
+                    version: version.to_string(),
+                    build_info: read_build_info(&install_dir),
+                    install_dir,
+                });
+            }
+        }
+    }
+
+    tools.sort_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
+    Ok(tools)
+}
+
+/// Returns the tools that were built with `go_version`.
+pub fn tools_built_with<'a>(
+    tools: &'a [InstalledTool],
+    go_version: &str,
+) -> Vec<&'a InstalledTool> {
+    tools
+        .iter()
+        .filter(|t| t.build_info.as_ref().and_then(|info| info.go.as_deref()) == Some(go_version))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn install_tool(versions_dir: &Path, tool: &str, version: &str, go: Option<&str>) {
+        let install_dir = versions_dir.join(tool).join(version);
+        fs::create_dir_all(install_dir.join("bin")).unwrap();
+        if let Some(go) = go {
+            write_build_info(
+                &install_dir,
+                &ToolBuildInfo {
+                    package: format!("example.com/cmd/{}", tool),
+                    go: Some(go.to_string()),
+                },
+            )
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn lists_tools_and_skips_go_toolchains() {
+        let dir = tempdir().unwrap();
+        let versions_dir = dir.path();
+        fs::create_dir_all(versions_dir.join("1.22.3").join("go").join("bin")).unwrap();
+        install_tool(versions_dir, "gopls", "v0.16.1", Some("1.22.3"));
+        install_tool(versions_dir, "dlv", "v1.22.0", None);
+
+        let tools = list_installed_tools(versions_dir).unwrap();
+
+        assert_eq!(tools.len(), 2);
+        assert_eq!(tools[0].name, "dlv");
+        assert_eq!(tools[0].build_info, None);
+        assert_eq!(tools[1].name, "gopls");
+        assert_eq!(
+            tools[1].build_info.as_ref().unwrap().go.as_deref(),
+            Some("1.22.3")
+        );
+    }
+
+    #[test]
+    fn filters_tools_by_build_go_version() {
+        let dir = tempdir().unwrap();
+        let versions_dir = dir.path();
+        install_tool(versions_dir, "gopls", "v0.16.1", Some("1.22.3"));
+        install_tool(versions_dir, "air", "v1.61.0", Some("1.23.0"));
+
+        let tools = list_installed_tools(versions_dir).unwrap();
+        let built = tools_built_with(&tools, "1.22.3");
+
+        assert_eq!(built.len(), 1);
+        assert_eq!(built[0].name, "gopls");
+    }
+}
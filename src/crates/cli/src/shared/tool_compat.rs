@@ -0,0 +1,181 @@
+use futures_util::future::join_all;
+use golta_core::go_mod::parse_go_mod;
+use golta_core::gover::compare_go_versions;
+use semver::Version;
+use std::cmp::Ordering;
+use std::error::Error;
+
+/// How many of the newest tool releases are inspected when looking for a compatible one.
+const COMPATIBLE_SEARCH_LIMIT: usize = 20;
+
+/// The Go requirements declared in a tool module's go.mod.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct GoRequirement {
+    /// The `go` directive: the minimum Go version needed to build the module.
+    pub go: Option<String>,
+    /// The `toolchain` directive: the toolchain the module authors suggest.
+    pub toolchain: Option<String>,
+}
+
+impl GoRequirement {
+    /// Returns true when `active_go` is new enough to build the module.
+    pub fn is_satisfied_by(&self, active_go: &str) -> bool {
+        match &self.go {
+            Some(required) => compare_go_versions(active_go, required) != Ordering::Less,
+            None => true,
+        }
+    }
+}
+
+/// Fetches the go.mod of `module@version` from the module proxy and reads its Go requirements.
+pub async fn fetch_go_requirement(
+    module: &str,
+    version: &str,
+) -> Result<GoRequirement, Box<dyn Error>> {
+    let url = format!(
+        "https://proxy.golang.org/{}/@v/{}.mod",
+        escape_module_path(module),
+        version
+    );
+    let content = reqwest::get(&url).await?.error_for_status()?.text().await?;
+    let parsed = parse_go_mod(&content);
+    Ok(GoRequirement {
+        go: parsed.go,
+        toolchain: parsed.toolchain,
+    })
+}
+
+/// Fetches the Go requirements of several versions of `module` concurrently.
+pub async fn fetch_go_requirements(
+    module: &str,
+    versions: &[String],
+) -> Vec<Option<GoRequirement>> {
+    join_all(
+        versions
+            .iter()
+            .map(|version| async move { fetch_go_requirement(module, version).await.ok() }),
+    )
+    .await
+}
+
+/// Finds the newest stable release of `module` that can be built with `active_go`.
+pub async fn newest_compatible_version(
+    module: &str,
+    available: &[String],
+    active_go: &str,
+) -> Option<String> {
+    let candidates: Vec<String> = newest_stable_first(available)
+        .into_iter()
+        .take(COMPATIBLE_SEARCH_LIMIT)
+        .collect();
+    let requirements = fetch_go_requirements(module, &candidates).await;
+    pick_newest_compatible(&candidates, &requirements, active_go)
+}
+
+/// Returns the first candidate whose requirement is known and satisfied.
+fn pick_newest_compatible(
+    candidates: &[String],
+    requirements: &[Option<GoRequirement>],
+    active_go: &str,
+) -> Option<String> {
+    candidates
+        .iter()
+        .zip(requirements)
+        .find(|(_, req)| {
+            req.as_ref()
+                .map(|r| r.is_satisfied_by(active_go))
+                .unwrap_or(false)
+        })
+        .map(|(version, _)| version.clone())
+}
+
+/// Sorts module versions newest first and drops pre-releases.
+fn newest_stable_first(versions: &[String]) -> Vec<String> {
+    let mut parsed: Vec<(Version, &String)> = versions
+        .iter()
+        .filter_map(|v| {
+            Version::parse(v.trim_start_matches('v'))
+                .ok()
+                .map(|parsed| (parsed, v))
+        })
+        .filter(|(parsed, _)| parsed.pre.is_empty())
+        .collect();
+    parsed.sort_by(|(a, _), (b, _)| b.cmp(a));
+    parsed.into_iter().map(|(_, v)| v.clone()).collect()
+}
+
+/// Applies the module proxy's case encoding (`A` becomes `!a`).
+fn escape_module_path(module: &str) -> String {
+    let mut escaped = String::with_capacity(module.len());
+    for c in module.chars() {
+        if c.is_ascii_uppercase() {
+            escaped.push('!');
+            escaped.push(c.to_ascii_lowercase());
+        } else {
+            escaped.push(c);
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn requirement(go: &str) -> Option<GoRequirement> {
+        Some(GoRequirement {
+            go: Some(go.to_string()),
+            toolchain: None,
+        })
+    }
+
+    #[test]
+    fn requirement_uses_go_directive_as_minimum() {
+        let req = GoRequirement {
+            go: Some("1.23.4".to_string()),
+            toolchain: Some("1.24.0".to_string()),
+        };
+
+        assert!(req.is_satisfied_by("1.23.4"));
+        assert!(req.is_satisfied_by("1.24.1"));
+        assert!(!req.is_satisfied_by("1.22.10"));
+        assert!(GoRequirement::default().is_satisfied_by("1.18"));
+    }
+
+    #[test]
+    fn picks_newest_compatible_candidate() {
+        let candidates = vec![
+            "v0.18.1".to_string(),
+            "v0.17.0".to_string(),
+            "v0.16.2".to_string(),
+        ];
+        let requirements = vec![requirement("1.23.4"), None, requirement("1.22")];
+
+        let picked = pick_newest_compatible(&candidates, &requirements, "1.22.3");
+
+        assert_eq!(picked.as_deref(), Some("v0.16.2"));
+    }
+
+    #[test]
+    fn sorts_versions_newest_first_without_prereleases() {
+        let versions = vec![
+            "v0.9.0".to_string(),
+            "v0.16.2".to_string(),
+            "v0.17.0-pre.1".to_string(),
+            "v0.10.1".to_string(),
+        ];
+
+        assert_eq!(
+            newest_stable_first(&versions),
+            vec!["v0.16.2", "v0.10.1", "v0.9.0"]
+        );
+    }
+
+    #[test]
+    fn escapes_uppercase_module_paths() {
+        assert_eq!(
+            escape_module_path("github.com/BurntSushi/toml"),
+            "github.com/!burnt!sushi/toml"
+        );
+    }
+}
@@ -0,0 +1,82 @@
+//! Measures the lookup the `go` shim does on every invocation (the Go version and the
+//! project `env`), with and without the on-disk cache. Run with `cargo bench -p golta-core`.
+
+use golta_core::cache::{find_shim_lookup_cached, NO_CACHE_ENV};
+use std::env;
+use std::fs::{self, File};
+use std::path::Path;
+use std::time::{Duration, Instant, SystemTime};
+
+const ITERATIONS: u32 = 2_000;
+
+fn main() {
+    let home = tempfile::tempdir().unwrap();
+    let project = tempfile::tempdir().unwrap();
+
+    // A module root with a pin file and go.mod, and a working directory a few levels down,
+    // like an editor running `go` inside a package.
+    let root = project.path();
+    let cwd = root
+        .join("internal")
+        .join("service")
+        .join("handler")
+        .join("v2");
+    fs::create_dir_all(&cwd).unwrap();
+    fs::write(
+        root.join(".golta.json"),
+        r#"{"tools":{"gopls":"v0.16.1","staticcheck":"2024.1.1"},"env":{"CGO_ENABLED":"0"}}"#,
+    )
+    .unwrap();
+    let mut go_mod =
+        String::from("module example.com/app\n\ngo 1.22\n\ntoolchain go1.22.5\n\nrequire (\n");
+    for i in 0..200 {
+        go_mod.push_str(&format!("\texample.com/dep{} v1.{}.0\n", i, i));
+    }
+    go_mod.push_str(")\n");
+    fs::write(root.join("go.mod"), go_mod).unwrap();
+
+    // Cache entries are only written for files outside the racy mtime window.
+    let old = SystemTime::now() - Duration::from_secs(60);
+    for file in [".golta.json", "go.mod"] {
+        File::options()
+            .write(true)
+            .open(root.join(file))
+            .unwrap()
+            .set_modified(old)
+            .unwrap();
+    }
+
+    // The same call the shim makes, first with the cache bypassed as GOLTA_NO_CACHE does.
+    env::set_var(NO_CACHE_ENV, "1");
+    let uncached = measure(|| find_shim_lookup_cached(&cwd, home.path()));
+    env::remove_var(NO_CACHE_ENV);
+    // Prime the cache, then measure hits.
+    find_shim_lookup_cached(&cwd, home.path()).unwrap();
+    let cached = measure(|| find_shim_lookup_cached(&cwd, home.path()));
+
+    println!(
+        "shim lookup without cache: {:>8.1} us/call",
+        micros(uncached)
+    );
+    println!("shim lookup with cache:    {:>8.1} us/call", micros(cached));
+    assert_cached_entry(home.path());
+}
+
+fn measure<T, E: std::fmt::Debug>(f: impl Fn() -> Result<T, E>) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f().unwrap();
+    }
+    start.elapsed() / ITERATIONS
+}
+
+fn micros(d: Duration) -> f64 {
+    d.as_secs_f64() * 1_000_000.0
+}
+
+fn assert_cached_entry(home: &Path) {
+    let entries = fs::read_dir(golta_core::cache::cache_dir(home))
+        .unwrap()
+        .count();
+    assert_eq!(entries, 1, "expected exactly one cache entry");
+}
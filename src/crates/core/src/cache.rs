@@ -0,0 +1,390 @@
+//! On-disk cache of [`Resolution`]s, together with the project `env`, so the `go` shim
+//! can skip walking the tree and parsing pin files on every invocation.
+//!
+//! An entry records every path resolution looked at, together with its modification
+//! time and size (or its absence). The entry is reused only while all of those stamps
+//! are unchanged, so creating, deleting or editing any consulted file invalidates it.
+
+use crate::pinned_version::{find_project_env_from, PinFileSystem, ProjectEnv, StdFs};
+use crate::resolve::{find_go_version_with, Resolution, ResolveEnv, VersionSource};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::hash_map::DefaultHasher;
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Set to any value other than `0` to bypass the cache.
+pub const NO_CACHE_ENV: &str = "GOLTA_NO_CACHE";
+
+/// The most cache entries kept; writing a new one removes the oldest beyond this.
+const MAX_ENTRIES: usize = 256;
+
+/// Files modified this recently are not trusted to have a distinct mtime yet,
+/// so resolutions that depend on them are not cached.
+const RACY_WINDOW: Duration = Duration::from_secs(2);
+
+/// Modification time (nanoseconds since the epoch) and size of a file, or `None` if missing.
+type Stamp = Option<(u128, u64)>;
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    key: String,
+    version: String,
+    source: VersionSource,
+    path: Option<PathBuf>,
+    line: Option<usize>,
+    env: Result<Option<ProjectEnv>, String>,
+    consulted: Vec<(PathBuf, Stamp)>,
+}
+
+/// What the shim reads for a directory: the Go version and the project `env`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShimLookup {
+    pub resolution: Option<Resolution>,
+    /// The `env` of the nearest `.golta.json`, or why that file could not be read.
+    pub env: Result<Option<ProjectEnv>, String>,
+}
+
+/// Same as [`crate::resolve::find_go_version`] plus the project `env`, reusing a cached
+/// result while none of the files they depend on have changed.
+pub fn find_shim_lookup_cached(
+    start_dir: &Path,
+    home_dir: &Path,
+) -> Result<ShimLookup, Box<dyn Error>> {
+    let resolve_env = ResolveEnv::from_env();
+    let bypass = env::var(NO_CACHE_ENV).is_ok_and(|v| !v.is_empty() && v != "0");
+    if bypass || resolve_env.session_override.is_some() {
+        return lookup(&StdFs, &resolve_env, start_dir, home_dir);
+    }
+
+    let key = cache_key(start_dir, home_dir, &resolve_env);
+    let cache_file = cache_file(home_dir, &key);
+    if let Some(lookup) = load(&cache_file, &key) {
+        return Ok(lookup);
+    }
+
+    let recorder = RecordingFs::new(StdFs);
+    let lookup = lookup(&recorder, &resolve_env, start_dir, home_dir)?;
+    if lookup.resolution.is_some() {
+        // The cache is an optimisation; failing to write it must not fail the command.
+        let _ = store(&cache_file, &key, &lookup, recorder.into_consulted());
+    }
+    Ok(lookup)
+}
+
+fn lookup<F: PinFileSystem>(
+    fs: &F,
+    resolve_env: &ResolveEnv,
+    start_dir: &Path,
+    home_dir: &Path,
+) -> Result<ShimLookup, Box<dyn Error>> {
+    Ok(ShimLookup {
+        resolution: find_go_version_with(fs, resolve_env, start_dir, home_dir)?,
+        env: find_project_env_from(fs, start_dir).map_err(|e| e.to_string()),
+    })
+}
+
+/// Returns the directory holding resolution cache entries.
+pub fn cache_dir(home_dir: &Path) -> PathBuf {
+    home_dir.join(".golta").join("cache").join("resolve")
+}
+
+fn cache_key(start_dir: &Path, home_dir: &Path, resolve_env: &ResolveEnv) -> String {
+    format!(
+        "{}\n{}\n{:?}",
+        start_dir.display(),
+        home_dir.display(),
+        resolve_env.gowork
+    )
+}
+
+fn cache_file(home_dir: &Path, key: &str) -> PathBuf {
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    cache_dir(home_dir).join(format!("{:016x}.json", hasher.finish()))
+}
+
+fn load(cache_file: &Path, key: &str) -> Option<ShimLookup> {
+    let content = fs::read_to_string(cache_file).ok()?;
+    let entry: CacheEntry = serde_json::from_str(&content).ok()?;
+    // The key is stored in the entry so that hash collisions are never served.
+    if entry.key != key {
+        return None;
+    }
+    if entry
+        .consulted
+        .iter()
+        .any(|(path, stamp)| stamp_of(path) != *stamp)
+    {
+        return None;
+    }
+    Some(ShimLookup {
+        resolution: Some(Resolution {
+            version: entry.version,
+            source: entry.source,
+            path: entry.path,
+            line: entry.line,
+        }),
+        env: entry.env,
+    })
+}
+
+fn store(
+    cache_file: &Path,
+    key: &str,
+    lookup: &ShimLookup,
+    consulted: Vec<(PathBuf, Stamp)>,
+) -> Result<(), Box<dyn Error>> {
+    let Some(resolution) = &lookup.resolution else {
+        return Ok(());
+    };
+    if consulted.iter().any(|(_, stamp)| is_racy(stamp)) {
+        return Ok(());
+    }
+
+    let entry = CacheEntry {
+        key: key.to_string(),
+        version: resolution.version.clone(),
+        source: resolution.source,
+        path: resolution.path.clone(),
+        line: resolution.line,
+        env: lookup.env.clone(),
+        consulted,
+    };
+    let dir = cache_file.parent().ok_or("cache file has no parent")?;
+    fs::create_dir_all(dir)?;
+
+    // Write to a private temporary file and rename it into place, so concurrent shims
+    // never read a half-written entry.
+    let tmp = cache_file.with_extension(format!("json.{}.tmp", process::id()));
+    fs::write(&tmp, serde_json::to_vec(&entry)?)?;
+    if let Err(e) = fs::rename(&tmp, cache_file) {
+        let _ = fs::remove_file(&tmp);
+        return Err(e.into());
+    }
+    evict(dir, MAX_ENTRIES)
+}
+
+/// Removes the least recently written entries of `dir` until at most `keep` are left, so
+/// visiting many directories does not grow the cache without bound.
+fn evict(dir: &Path, keep: usize) -> Result<(), Box<dyn Error>> {
+    let mut entries = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "json") {
+            entries.push((stamp_of(&path).map_or(0, |(mtime, _)| mtime), path));
+        }
+    }
+    if entries.len() <= keep {
+        return Ok(());
+    }
+
+    entries.sort();
+    for (_, path) in &entries[..entries.len() - keep] {
+        // Another shim may have removed or replaced it already.
+        let _ = fs::remove_file(path);
+    }
+    Ok(())
+}
+
+fn stamp_of(path: &Path) -> Stamp {
+    let meta = fs::metadata(path).ok()?;
+    let mtime = meta.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+    Some((mtime.as_nanos(), meta.len()))
+}
+
+fn is_racy(stamp: &Stamp) -> bool {
+    let Some((mtime, _)) = stamp else {
+        return false;
+    };
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    now.saturating_sub(*mtime) < RACY_WINDOW.as_nanos()
+}
+
+/// Wraps a filesystem and remembers the stamp of every path resolution asks about.
+struct RecordingFs<F> {
+    inner: F,
+    consulted: RefCell<Vec<(PathBuf, Stamp)>>,
+}
+
+impl<F: PinFileSystem> RecordingFs<F> {
+    fn new(inner: F) -> Self {
+        Self {
+            inner,
+            consulted: RefCell::new(Vec::new()),
+        }
+    }
+
+    fn record(&self, path: &Path) {
+        let mut consulted = self.consulted.borrow_mut();
+        if !consulted.iter().any(|(p, _)| p == path) {
+            consulted.push((path.to_path_buf(), stamp_of(path)));
+        }
+    }
+
+    fn into_consulted(self) -> Vec<(PathBuf, Stamp)> {
+        self.consulted.into_inner()
+    }
+}
+
+impl<F: PinFileSystem> PinFileSystem for RecordingFs<F> {
+    fn exists(&self, path: &Path) -> bool {
+        self.record(path);
+        self.inner.exists(path)
+    }
+
+    fn read_to_string(&self, path: &Path) -> Result<String, Box<dyn Error>> {
+        self.record(path);
+        self.inner.read_to_string(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn resolve_and_store(start: &Path, home: &Path) -> (PathBuf, String) {
+        let resolve_env = ResolveEnv::default();
+        let key = cache_key(start, home, &resolve_env);
+        let file = cache_file(home, &key);
+        let recorder = RecordingFs::new(StdFs);
+        let found = lookup(&recorder, &resolve_env, start, home).unwrap();
+        let resolution = found.resolution.unwrap();
+        // Bypass the racy check: the test files were just written.
+        let consulted = recorder.into_consulted();
+        let entry = CacheEntry {
+            key: key.clone(),
+            version: resolution.version,
+            source: resolution.source,
+            path: resolution.path,
+            line: resolution.line,
+            env: found.env,
+            consulted,
+        };
+        fs::create_dir_all(file.parent().unwrap()).unwrap();
+        fs::write(&file, serde_json::to_vec(&entry).unwrap()).unwrap();
+        (file, key)
+    }
+
+    #[test]
+    fn reuses_entry_until_a_consulted_file_changes() {
+        let home = tempdir().unwrap();
+        let project = tempdir().unwrap();
+        let sub = project.path().join("sub");
+        fs::create_dir_all(&sub).unwrap();
+        fs::write(project.path().join("go.mod"), "module m\n\ngo 1.22.1\n").unwrap();
+
+        let (file, key) = resolve_and_store(&sub, home.path());
+        let version = |file, key| load(file, key).and_then(|l| l.resolution).unwrap().version;
+        assert_eq!(version(&file, &key), "1.22.1");
+
+        // A pin file appearing in a directory that was walked invalidates the entry.
+        fs::write(sub.join(".go-version"), "1.21.0\n").unwrap();
+        assert!(load(&file, &key).is_none());
+    }
+
+    #[test]
+    fn caches_project_env_and_degrades_invalid_files() {
+        let home = tempdir().unwrap();
+        let project = tempdir().unwrap();
+        let sub = project.path().join("sub");
+        fs::create_dir_all(&sub).unwrap();
+        let pin = project.path().join(".golta.json");
+        fs::write(&pin, r#"{"go":"1.22.1","env":{"CGO_ENABLED":"0"}}"#).unwrap();
+
+        let (file, key) = resolve_and_store(&sub, home.path());
+        let env = load(&file, &key).unwrap().env.unwrap().unwrap();
+        assert_eq!(env.path, pin);
+        assert_eq!(env.vars["CGO_ENABLED"], "0");
+
+        // A broken .golta.json above the file that decided the version is reported,
+        // not fatal.
+        fs::write(sub.join(".go-version"), "1.21.0\n").unwrap();
+        fs::write(&pin, "{\"Go\": 1}").unwrap();
+        let found = lookup(&StdFs, &ResolveEnv::default(), &sub, home.path()).unwrap();
+        assert!(found.resolution.is_some());
+        assert!(found.env.unwrap_err().contains("unknown field `Go`"));
+    }
+
+    #[test]
+    fn edited_file_invalidates_entry() {
+        let home = tempdir().unwrap();
+        let project = tempdir().unwrap();
+        let go_mod = project.path().join("go.mod");
+        fs::write(&go_mod, "module m\n\ngo 1.22.1\n").unwrap();
+
+        let (file, key) = resolve_and_store(project.path(), home.path());
+        fs::write(&go_mod, "module m\n\ngo 1.23.10\n").unwrap();
+
+        assert!(load(&file, &key).is_none());
+    }
+
+    #[test]
+    fn ignores_entries_for_other_keys() {
+        let home = tempdir().unwrap();
+        let project = tempdir().unwrap();
+        fs::write(project.path().join(".go-version"), "1.22.1\n").unwrap();
+
+        let (file, _) = resolve_and_store(project.path(), home.path());
+
+        assert!(load(&file, "another key").is_none());
+    }
+
+    #[test]
+    fn evicts_oldest_entries_beyond_the_cap() {
+        let dir = tempdir().unwrap();
+        let old = SystemTime::now() - Duration::from_secs(60);
+        for (i, name) in ["a.json", "b.json", "c.json"].into_iter().enumerate() {
+            let path = dir.path().join(name);
+            fs::write(&path, "{}").unwrap();
+            fs::File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(old + Duration::from_secs(i as u64))
+                .unwrap();
+        }
+
+        evict(dir.path(), 2).unwrap();
+
+        assert!(!dir.path().join("a.json").exists());
+        assert!(dir.path().join("b.json").exists());
+        assert!(dir.path().join("c.json").exists());
+    }
+
+    #[test]
+    fn recent_files_are_not_cached() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("entry.json");
+        let fresh = dir.path().join("go.mod");
+        fs::write(&fresh, "go 1.22.1\n").unwrap();
+        let lookup = ShimLookup {
+            resolution: Some(Resolution {
+                version: "1.22.1".to_string(),
+                source: VersionSource::GoMod,
+                path: Some(fresh.clone()),
+                line: Some(1),
+            }),
+            env: Ok(None),
+        };
+
+        store(
+            &file,
+            "key",
+            &lookup,
+            vec![(fresh.clone(), stamp_of(&fresh))],
+        )
+        .unwrap();
+
+        assert!(!file.exists());
+    }
+}
@@ -0,0 +1,205 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Environment variable that overrides the configured auto-install policy.
+pub const AUTO_INSTALL_ENV: &str = "GOLTA_AUTO_INSTALL";
+
+/// What the shim does when the selected Go version is not installed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AutoInstall {
+    /// Fail with a hint to run `golta install`.
+    Never,
+    /// Ask on the terminal; behaves like `never` when there is no terminal.
+    #[default]
+    Prompt,
+    /// Install without asking.
+    Always,
+}
+
+impl FromStr for AutoInstall {
+    type Err = String;
+
+    /// Accepts the policy names, plus the older `1`/`true` and `0`/`false` values.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "never" | "0" | "false" => Ok(Self::Never),
+            "prompt" => Ok(Self::Prompt),
+            "always" | "1" | "true" => Ok(Self::Always),
+            other => Err(format!(
+                "Invalid auto-install policy '{}'. Use never, prompt or always.",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for AutoInstall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Never => "never",
+            Self::Prompt => "prompt",
+            Self::Always => "always",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A Go environment variable that can be given a separate directory per Go version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum IsolatedVar {
+    Gobin,
+    Gopath,
+    Gocache,
+}
+
+impl IsolatedVar {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Gobin => "GOBIN",
+            Self::Gopath => "GOPATH",
+            Self::Gocache => "GOCACHE",
+        }
+    }
+
+    /// The directory this variable points at for `version`, under `~/.golta/isolation/<version>`.
+    pub fn dir(self, home_dir: &Path, version: &str) -> PathBuf {
+        let name = match self {
+            Self::Gobin => "bin",
+            Self::Gopath => "gopath",
+            Self::Gocache => "cache",
+        };
+        isolation_dir(home_dir, version).join(name)
+    }
+}
+
+/// User settings stored in `~/.golta/config.json`. Unknown keys are kept when saving.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GoltaConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_install: Option<AutoInstall>,
+    /// Variables the shim points at per-version directories, e.g. `["GOBIN", "GOPATH"]`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub isolate: Vec<IsolatedVar>,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+impl GoltaConfig {
+    /// The effective auto-install policy: `GOLTA_AUTO_INSTALL`, then the config file,
+    /// then `prompt`. An invalid environment value is reported instead of ignored.
+    pub fn auto_install_policy(&self, env_value: Option<&str>) -> Result<AutoInstall, String> {
+        match env_value.map(str::trim).filter(|v| !v.is_empty()) {
+            Some(value) => value.parse(),
+            None => Ok(self.auto_install.unwrap_or_default()),
+        }
+    }
+}
+
+pub fn config_path(home_dir: &Path) -> PathBuf {
+    home_dir.join(".golta").join("config.json")
+}
+
+/// Returns the directory holding the isolated `GOBIN`/`GOPATH`/`GOCACHE` of `version`.
+pub fn isolation_dir(home_dir: &Path, version: &str) -> PathBuf {
+    home_dir.join(".golta").join("isolation").join(version)
+}
+
+/// Reads `~/.golta/config.json`, returning the defaults when it does not exist.
+pub fn load_config(home_dir: &Path) -> Result<GoltaConfig, Box<dyn Error>> {
+    let path = config_path(home_dir);
+    if !path.exists() {
+        return Ok(GoltaConfig::default());
+    }
+    let content = fs::read_to_string(&path)?;
+    if content.trim().is_empty() {
+        return Ok(GoltaConfig::default());
+    }
+    serde_json::from_str(&content).map_err(|e| format!("Invalid {}: {}", path.display(), e).into())
+}
+
+pub fn save_config(home_dir: &Path, config: &GoltaConfig) -> Result<(), Box<dyn Error>> {
+    let path = config_path(home_dir);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, serde_json::to_string_pretty(config)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn env_value_beats_config_file() {
+        let config = GoltaConfig {
+            auto_install: Some(AutoInstall::Never),
+            ..GoltaConfig::default()
+        };
+
+        assert_eq!(config.auto_install_policy(None), Ok(AutoInstall::Never));
+        assert_eq!(
+            config.auto_install_policy(Some("always")),
+            Ok(AutoInstall::Always)
+        );
+        assert_eq!(
+            config.auto_install_policy(Some("1")),
+            Ok(AutoInstall::Always)
+        );
+        assert!(config.auto_install_policy(Some("sometimes")).is_err());
+        assert_eq!(
+            GoltaConfig::default().auto_install_policy(Some("")),
+            Ok(AutoInstall::Prompt)
+        );
+    }
+
+    #[test]
+    fn saving_keeps_unknown_keys() {
+        let home = tempdir().unwrap();
+        fs::create_dir_all(home.path().join(".golta")).unwrap();
+        fs::write(
+            config_path(home.path()),
+            r#"{"future_setting": 3, "auto_install": "prompt"}"#,
+        )
+        .unwrap();
+
+        let mut config = load_config(home.path()).unwrap();
+        config.auto_install = Some(AutoInstall::Always);
+        save_config(home.path(), &config).unwrap();
+
+        let saved = fs::read_to_string(config_path(home.path())).unwrap();
+        assert!(saved.contains("\"auto_install\": \"always\""));
+        assert!(saved.contains("\"future_setting\": 3"));
+    }
+
+    #[test]
+    fn reads_isolated_variables() {
+        let config: GoltaConfig =
+            serde_json::from_str(r#"{"isolate": ["GOBIN", "GOCACHE"]}"#).unwrap();
+
+        assert_eq!(
+            config.isolate,
+            vec![IsolatedVar::Gobin, IsolatedVar::Gocache]
+        );
+        assert_eq!(
+            IsolatedVar::Gobin.dir(Path::new("/home/u"), "1.22.3"),
+            Path::new("/home/u/.golta/isolation/1.22.3/bin")
+        );
+        assert!(serde_json::from_str::<GoltaConfig>(r#"{"isolate": ["GOROOT"]}"#).is_err());
+    }
+
+    #[test]
+    fn missing_config_uses_defaults() {
+        let home = tempdir().unwrap();
+
+        assert_eq!(load_config(home.path()).unwrap(), GoltaConfig::default());
+    }
+}
@@ -0,0 +1,281 @@
+use crate::gover::compare_go_versions;
+use std::cmp::Ordering;
+use std::env;
+use std::path::{Path, PathBuf};
+
+/// A `require` entry from go.mod.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Requirement {
+    pub path: String,
+    pub version: String,
+}
+
+/// The parts of a go.mod file that golta cares about.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct GoModFile {
+    pub go: Option<String>,
+    pub toolchain: Option<String>,
+    pub requires: Vec<Requirement>,
+    pub tools: Vec<String>,
+}
+
+impl GoModFile {
+    /// Returns the Go version the module asks for: the `toolchain` directive
+    /// when it is newer than the `go` directive, otherwise the `go` directive
+    /// (which Go treats as a minimum, not an exact release).
+    pub fn selected_version(&self) -> Option<String> {
+        match (&self.go, &self.toolchain) {
+            (Some(go), Some(toolchain)) => {
+                if compare_go_versions(toolchain, go) == Ordering::Greater {
+                    Some(toolchain.clone())
+                } else {
+                    Some(go.clone())
+                }
+            }
+            (go, toolchain) => toolchain.clone().or_else(|| go.clone()),
+        }
+    }
+
+    /// Returns the required version of the module that provides `package`,
+    /// picking the longest matching module path.
+    pub fn version_for_package(&self, package: &str) -> Option<&Requirement> {
+        self.requires
+            .iter()
+            .filter(|r| package == r.path || package.starts_with(&format!("{}/", r.path)))
+            .max_by_key(|r| r.path.len())
+    }
+}
+
+/// How `GOWORK` selects the workspace file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum GoWork {
+    /// Unset or empty: the nearest go.work above the current directory applies.
+    #[default]
+    Auto,
+    /// `GOWORK=off`: workspaces are ignored.
+    Off,
+    /// `GOWORK=<path>`: that file is the workspace.
+    File(PathBuf),
+}
+
+impl GoWork {
+    pub fn from_env() -> Self {
+        Self::from_value(env::var("GOWORK").ok().as_deref())
+    }
+
+    pub fn from_value(value: Option<&str>) -> Self {
+        match value.map(str::trim) {
+            None | Some("") => Self::Auto,
+            Some("off") => Self::Off,
+            Some(path) => Self::File(PathBuf::from(path)),
+        }
+    }
+}
+
+/// Parses `go`, `toolchain`, `require` and `tool` directives, both in single-line and block form.
+/// go.work files share the syntax, so their `go` and `toolchain` lines are read the same way.
+pub fn parse_go_mod(content: &str) -> GoModFile {
+    let mut parsed = GoModFile::default();
+    let mut block: Option<String> = None;
+
+    for line in content.lines() {
+        // Strip comments and whitespace.
+        let line = line.split("//").next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let fields: Vec<&str> = if let Some(verb) = &block {
+            if line == ")" {
+                block = None;
+                continue;
+            }
+            std::iter::once(verb.as_str())
+                .chain(line.split_whitespace())
+                .collect()
+        } else {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() == 2 && fields[1] == "(" {
+                block = Some(fields[0].to_string());
+                continue;
+            }
+            fields
+        };
+
+        match fields.as_slice() {
+            ["go", version, ..] => parsed.go = Some(version.to_string()),
+            ["toolchain", name, ..] => {
+                parsed.toolchain = Some(name.strip_prefix("go").unwrap_or(name).to_string())
+            }
+            ["require", path, version, ..] => parsed.requires.push(Requirement {
+                path: unquote(path),
+                version: version.to_string(),
+            }),
+            ["tool", path, ..] => parsed.tools.push(unquote(path)),
+            _ => {}
+        }
+    }
+
+    parsed
+}
+
+/// Walks up from `start_dir` and returns the first directory that contains a go.mod file.
+pub fn find_module_root(start_dir: &Path) -> Option<PathBuf> {
+    let mut current_dir = start_dir.to_path_buf();
+    loop {
+        if current_dir.join("go.mod").is_file() {
+            return Some(current_dir);
+        }
+        if !current_dir.pop() {
+            return None;
+        }
+    }
+}
+
+/// Returns the file whose `go` and `toolchain` lines apply to `start_dir`: the workspace
+/// file when the directory is inside a workspace, otherwise the module's go.mod.
+pub fn find_go_version_file(start_dir: &Path, gowork: &GoWork) -> Option<PathBuf> {
+    match gowork {
+        GoWork::File(path) => return Some(path.clone()),
+        GoWork::Auto => {
+            if let Some(work) = find_go_work(start_dir) {
+                return Some(work);
+            }
+        }
+        GoWork::Off => {}
+    }
+    find_module_root(start_dir).map(|root| root.join("go.mod"))
+}
+
+/// Walks up from `start_dir` and returns the first go.work file, like the go command does.
+pub fn find_go_work(start_dir: &Path) -> Option<PathBuf> {
+    let mut current_dir = start_dir.to_path_buf();
+    loop {
+        let work = current_dir.join("go.work");
+        if work.is_file() {
+            return Some(work);
+        }
+        if !current_dir.pop() {
+            return None;
+        }
+    }
+}
+
+fn unquote(s: &str) -> String {
+    s.trim_matches('"').trim_matches('`').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GO_MOD: &str = r#"module example.com/app
+
+go 1.24
+
+require github.com/go-delve/delve v1.24.0
+
+require (
+	golang.org/x/tools/gopls v0.18.1 // indirect
+	honnef.co/go/tools v0.6.0
+)
+
+tool golang.org/x/tools/gopls
+
+tool (
+	// linters
+	honnef.co/go/tools/cmd/staticcheck
+)
+"#;
+
+    #[test]
+    fn parses_require_and_tool_directives() {
+        let parsed = parse_go_mod(GO_MOD);
+
+        assert_eq!(parsed.go.as_deref(), Some("1.24"));
+        assert_eq!(
+            parsed.tools,
+            vec![
+                "golang.org/x/tools/gopls".to_string(),
+                "honnef.co/go/tools/cmd/staticcheck".to_string()
+            ]
+        );
+        assert_eq!(parsed.requires.len(), 3);
+        assert_eq!(
+            parsed.requires[1],
+            Requirement {
+                path: "golang.org/x/tools/gopls".to_string(),
+                version: "v0.18.1".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn finds_version_from_longest_module_prefix() {
+        let parsed = parse_go_mod(
+            "require (\n\tgolang.org/x/tools v0.30.0\n\tgolang.org/x/tools/gopls v0.18.1\n)\n",
+        );
+
+        let req = parsed
+            .version_for_package("golang.org/x/tools/gopls")
+            .unwrap();
+        assert_eq!(req.version, "v0.18.1");
+
+        let req = parsed
+            .version_for_package("golang.org/x/tools/cmd/stringer")
+            .unwrap();
+        assert_eq!(req.version, "v0.30.0");
+
+        assert!(parsed.version_for_package("golang.org/x/toolsx").is_none());
+    }
+
+    #[test]
+    fn reads_go_work_directives() {
+        let parsed =
+            parse_go_mod("go 1.23.0\n\ntoolchain go1.23.2\n\nuse (\n\t./api\n\t./web\n)\n");
+
+        assert_eq!(parsed.selected_version().as_deref(), Some("1.23.2"));
+        assert!(parsed.requires.is_empty());
+    }
+
+    #[test]
+    fn gowork_values() {
+        assert_eq!(GoWork::from_value(None), GoWork::Auto);
+        assert_eq!(GoWork::from_value(Some("")), GoWork::Auto);
+        assert_eq!(GoWork::from_value(Some("off")), GoWork::Off);
+        assert_eq!(
+            GoWork::from_value(Some("/src/go.work")),
+            GoWork::File(PathBuf::from("/src/go.work"))
+        );
+    }
+
+    #[test]
+    fn version_file_prefers_enclosing_workspace() {
+        let dir = tempfile::tempdir().unwrap();
+        let module = dir.path().join("api");
+        std::fs::create_dir_all(&module).unwrap();
+        std::fs::write(module.join("go.mod"), "module api\n\ngo 1.21\n").unwrap();
+        std::fs::write(dir.path().join("go.work"), "go 1.23\n\nuse ./api\n").unwrap();
+
+        assert_eq!(
+            find_go_version_file(&module, &GoWork::Auto),
+            Some(dir.path().join("go.work"))
+        );
+        assert_eq!(
+            find_go_version_file(&module, &GoWork::Off),
+            Some(module.join("go.mod"))
+        );
+    }
+
+    #[test]
+    fn prefers_toolchain_only_when_newer() {
+        let newer = parse_go_mod("go 1.22\ntoolchain go1.22.4\n");
+        assert_eq!(newer.selected_version().as_deref(), Some("1.22.4"));
+
+        let older = parse_go_mod("go 1.23.0\ntoolchain go1.22.4\n");
+        assert_eq!(older.selected_version().as_deref(), Some("1.23.0"));
+
+        let no_toolchain = parse_go_mod("go 1.22\n");
+        assert_eq!(no_toolchain.selected_version().as_deref(), Some("1.22"));
+    }
+}
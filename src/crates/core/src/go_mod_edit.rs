@@ -0,0 +1,183 @@
+//! Edits the `go` and `toolchain` directives of go.mod and go.work files in place,
+//! leaving every other line (comments, blocks, spacing) exactly as it was.
+
+use crate::gover::compare_go_versions;
+use std::cmp::Ordering;
+
+/// Rewrites `content` so that Go `version` is the module's toolchain, following Go's rules:
+///
+/// - a missing `go` line is added after `module` (or at the top of a go.work);
+/// - a `go` line newer than `version` is never lowered, since the module may rely on that
+///   language version; the content is returned unchanged and the caller should ask for a
+///   newer pin;
+/// - `toolchain go<version>` is set when `version` is newer than the `go` line, and the
+///   `toolchain` line is dropped when it would be redundant, as `go mod tidy` does.
+pub fn set_toolchain_version(content: &str, version: &str) -> String {
+    let mut lines: Vec<String> = content.split_inclusive('\n').map(str::to_string).collect();
+
+    let go_index = find_directive(&lines, "go");
+    let language = match go_index {
+        Some(index) => {
+            let current = directive_value(&lines[index]).unwrap_or_default();
+            if compare_go_versions(&current, version) == Ordering::Greater {
+                return content.to_string();
+            }
+            current
+        }
+        None => {
+            let at = find_directive(&lines, "module").map_or(0, |index| index + 1);
+            ensure_newline(&mut lines, at);
+            let mut inserted = vec![format!("go {}\n", version)];
+            if at > 0 {
+                inserted.insert(0, "\n".to_string());
+            } else if lines.first().is_some_and(|line| !line.trim().is_empty()) {
+                inserted.push("\n".to_string());
+            }
+            lines.splice(at..at, inserted);
+            version.to_string()
+        }
+    };
+
+    let needs_toolchain = compare_go_versions(version, &language) == Ordering::Greater;
+    let toolchain = format!("go{}", version);
+    match (find_directive(&lines, "toolchain"), needs_toolchain) {
+        (Some(index), true) => lines[index] = replace_value(&lines[index], &toolchain),
+        (Some(index), false) => {
+            lines.remove(index);
+            // Don't leave the blank line that separated it behind.
+            let blank = |line: Option<&String>| line.is_none_or(|line| line.trim().is_empty());
+            if index > 0 && blank(lines.get(index - 1)) && blank(lines.get(index)) {
+                lines.remove(index - 1);
+            }
+        }
+        (None, true) => {
+            let at = find_directive(&lines, "go").map_or(0, |index| index + 1);
+            ensure_newline(&mut lines, at);
+            lines.insert(at, format!("toolchain {}\n", toolchain));
+        }
+        (None, false) => {}
+    }
+
+    lines.concat()
+}
+
+/// Finds the first top-level line with `verb`, skipping the contents of `verb (` blocks.
+fn find_directive(lines: &[String], verb: &str) -> Option<usize> {
+    let mut in_block = false;
+    for (index, line) in lines.iter().enumerate() {
+        let code = line.split("//").next().unwrap_or("").trim();
+        if in_block {
+            in_block = code != ")";
+            continue;
+        }
+        let mut fields = code.split_whitespace();
+        let first = fields.next();
+        let second = fields.next();
+        if second == Some("(") {
+            in_block = true;
+            continue;
+        }
+        if first == Some(verb) && second.is_some() {
+            return Some(index);
+        }
+    }
+    None
+}
+
+fn directive_value(line: &str) -> Option<String> {
+    let code = line.split("//").next()?;
+    code.split_whitespace().nth(1).map(str::to_string)
+}
+
+/// Replaces the value of a `verb value // comment` line, keeping indentation, the
+/// comment and the line ending.
+fn replace_value(line: &str, value: &str) -> String {
+    let indent = line.len() - line.trim_start().len();
+    let rest = &line[indent..];
+    let verb_end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+    let after_verb = &rest[verb_end..];
+    let value_start = verb_end + (after_verb.len() - after_verb.trim_start().len());
+    let value_len = rest[value_start..]
+        .find(char::is_whitespace)
+        .unwrap_or(rest.len() - value_start);
+    format!(
+        "{}{}{}",
+        &line[..indent + value_start],
+        value,
+        &rest[value_start + value_len..]
+    )
+}
+
+/// Makes sure the line before `at` ends with a newline, so inserted lines start on their own.
+fn ensure_newline(lines: &mut [String], at: usize) {
+    if let Some(previous) = at.checked_sub(1).and_then(|index| lines.get_mut(index)) {
+        if !previous.ends_with('\n') {
+            previous.push('\n');
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adds_toolchain_above_language_version() {
+        let go_mod = "module example.com/m // the module\n\ngo 1.21 // language\n\nrequire (\n\tgo 1.0.0 // not a directive\n)\n";
+
+        let edited = set_toolchain_version(go_mod, "1.22.3");
+
+        assert_eq!(
+            edited,
+            "module example.com/m // the module\n\ngo 1.21 // language\ntoolchain go1.22.3\n\nrequire (\n\tgo 1.0.0 // not a directive\n)\n"
+        );
+    }
+
+    #[test]
+    fn leaves_newer_go_line_unchanged() {
+        let go_mod = "module m\n\ngo 1.23.0\n\ntoolchain go1.23.2\n";
+
+        assert_eq!(set_toolchain_version(go_mod, "1.22.3"), go_mod);
+    }
+
+    #[test]
+    fn drops_redundant_toolchain() {
+        let go_mod = "module m\n\ngo 1.22.3\n\ntoolchain go1.22.1\n";
+
+        assert_eq!(
+            set_toolchain_version(go_mod, "1.22.3"),
+            "module m\n\ngo 1.22.3\n"
+        );
+    }
+
+    #[test]
+    fn updates_existing_toolchain() {
+        let go_mod = "module m\n\ngo 1.21.0\n\ttoolchain  go1.21.5 // pinned\n";
+
+        let edited = set_toolchain_version(go_mod, "1.22.0");
+
+        assert_eq!(
+            edited,
+            "module m\n\ngo 1.21.0\n\ttoolchain  go1.22.0 // pinned\n"
+        );
+    }
+
+    #[test]
+    fn inserts_go_line_when_missing() {
+        assert_eq!(
+            set_toolchain_version("module m", "1.22.3"),
+            "module m\n\ngo 1.22.3\n"
+        );
+        assert_eq!(
+            set_toolchain_version("use ./a\n", "1.22.3"),
+            "go 1.22.3\n\nuse ./a\n"
+        );
+    }
+
+    #[test]
+    fn leaves_matching_file_unchanged() {
+        let go_mod = "module m\n\ngo 1.22.3\n";
+
+        assert_eq!(set_toolchain_version(go_mod, "1.22.3"), go_mod);
+    }
+}
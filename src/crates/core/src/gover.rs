@@ -0,0 +1,285 @@
+use std::cmp::Ordering;
+
+/// A Go toolchain or language version such as `1.21`, `1.21rc2` or `1.21.3`.
+///
+/// Ordering follows Go's `gover` package: a language version sorts before its
+/// pre-releases, which sort before the first release (`1.21 < 1.21rc1 < 1.21.0`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GoVersion {
+    major: u64,
+    minor: Option<u64>,
+    patch: Option<u64>,
+    kind: PreKind,
+    pre: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum PreKind {
+    Release,
+    Alpha,
+    Beta,
+    Rc,
+}
+
+impl GoVersion {
+    /// Parses a version with or without the `go` prefix. Returns `None` for invalid input.
+    pub fn parse(input: &str) -> Option<Self> {
+        let input = input.trim();
+        let input = input.strip_prefix("go").unwrap_or(input);
+
+        let (major, rest) = split_number(input)?;
+        if rest.is_empty() {
+            return Some(Self::new(major, None, None, PreKind::Release, None));
+        }
+
+        let rest = rest.strip_prefix('.')?;
+        let (minor, rest) = split_number(rest)?;
+        if rest.is_empty() {
+            return Some(Self::new(major, Some(minor), None, PreKind::Release, None));
+        }
+
+        if let Some(rest) = rest.strip_prefix('.') {
+            let (patch, rest) = split_number(rest)?;
+            if !rest.is_empty() {
+                return None;
+            }
+            return Some(Self::new(
+                major,
+                Some(minor),
+                Some(patch),
+                PreKind::Release,
+                None,
+            ));
+        }
+
+        for (prefix, kind) in [
+            ("alpha", PreKind::Alpha),
+            ("beta", PreKind::Beta),
+            ("rc", PreKind::Rc),
+        ] {
+            if let Some(rest) = rest.strip_prefix(prefix) {
+                let (pre, rest) = split_number(rest)?;
+                if !rest.is_empty() {
+                    return None;
+                }
+                return Some(Self::new(major, Some(minor), None, kind, Some(pre)));
+            }
+        }
+
+        None
+    }
+
+    /// Returns true for a language version such as `1.22`, which names a release
+    /// family rather than a single toolchain.
+    pub fn is_language_version(&self) -> bool {
+        self.minor.is_some() && self.patch.is_none() && self.kind == PreKind::Release
+    }
+
+    /// Returns true when `self` belongs to the family of the language version `lang`:
+    /// `1.22rc1` and `1.22.3` belong to `1.22`, and so does the pre-Go-1.21 release `1.20`.
+    fn is_in_family(&self, lang: &GoVersion) -> bool {
+        self.major == lang.major && self.minor == lang.minor
+    }
+
+    /// Orders an installed toolchain: a pre-Go-1.21 release named like a language version
+    /// (`1.20`) is that family's first release, newer than its pre-releases (`1.20rc1`).
+    fn toolchain_order(&self) -> Self {
+        let mut version = self.clone();
+        if version.is_language_version() {
+            version.patch = Some(0);
+        }
+        version
+    }
+
+    fn new(
+        major: u64,
+        minor: Option<u64>,
+        patch: Option<u64>,
+        kind: PreKind,
+        pre: Option<u64>,
+    ) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+            kind,
+            pre,
+        }
+    }
+}
+
+impl Ord for GoVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch, self.kind, self.pre).cmp(&(
+            other.major,
+            other.minor,
+            other.patch,
+            other.kind,
+            other.pre,
+        ))
+    }
+}
+
+impl PartialOrd for GoVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Compares two Go version strings. Invalid versions sort before valid ones.
+pub fn compare_go_versions(a: &str, b: &str) -> Ordering {
+    GoVersion::parse(a).cmp(&GoVersion::parse(b))
+}
+
+/// Picks the candidate that `spec` refers to.
+///
+/// A full version (`1.22.3`, `1.21rc2`) must match exactly. A language version
+/// (`1.22`) is a minimum within its family and selects the newest `1.22.x`; for
+/// releases before Go 1.21 this includes the first release itself, named `1.20`,
+/// which is newer than `1.20rc1`.
+/// `latest` and `stable` select the newest release that is not a pre-release, as
+/// `golta install go@latest` does. Specs that are not Go versions are compared as
+/// plain strings.
+pub fn select_version<'a, I>(spec: &str, candidates: I) -> Option<&'a str>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    if matches!(spec, "latest" | "stable") {
+        return candidates
+            .into_iter()
+            .filter_map(|c| GoVersion::parse(c).map(|parsed| (parsed, c)))
+            .filter(|(parsed, _)| parsed.kind == PreKind::Release)
+            .max_by_key(|(parsed, _)| parsed.toolchain_order())
+            .map(|(_, c)| c);
+    }
+
+    let Some(wanted) = GoVersion::parse(spec) else {
+        return candidates.into_iter().find(|c| *c == spec);
+    };
+
+    let mut matching = candidates
+        .into_iter()
+        .filter_map(|c| GoVersion::parse(c).map(|parsed| (parsed, c)));
+
+    if !wanted.is_language_version() {
+        return matching
+            .find(|(parsed, _)| *parsed == wanted)
+            .map(|(_, c)| c);
+    }
+
+    matching
+        .filter(|(parsed, _)| parsed.is_in_family(&wanted) && *parsed >= wanted)
+        .max_by_key(|(parsed, _)| parsed.toolchain_order())
+        .map(|(_, c)| c)
+}
+
+/// Picks the candidate for a spec that is a minimum, like the `go` line of go.mod.
+///
+/// A full version (`1.22.3`) selects the newest candidate of its family that is at
+/// least that version, so a `1.22.5` toolchain satisfies `go 1.22.3`. Other specs are
+/// selected as [`select_version`] does.
+pub fn select_minimum<'a, I>(spec: &str, candidates: I) -> Option<&'a str>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let wanted = match GoVersion::parse(spec) {
+        Some(wanted) if wanted.minor.is_some() && !wanted.is_language_version() => wanted,
+        _ => return select_version(spec, candidates),
+    };
+
+    candidates
+        .into_iter()
+        .filter_map(|c| GoVersion::parse(c).map(|parsed| (parsed, c)))
+        .map(|(parsed, c)| (parsed.toolchain_order(), c))
+        .filter(|(parsed, _)| parsed.is_in_family(&wanted) && *parsed >= wanted)
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(_, c)| c)
+}
+
+fn split_number(s: &str) -> Option<(u64, &str)> {
+    let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    if end == 0 {
+        return None;
+    }
+    let number = s[..end].parse().ok()?;
+    Some((number, &s[end..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn orders_language_prerelease_and_release_versions() {
+        let ordered = [
+            "1.20", "1.20.1", "1.21", "1.21rc1", "1.21rc2", "1.21.0", "1.21.5", "1.22",
+        ];
+        for pair in ordered.windows(2) {
+            assert_eq!(
+                compare_go_versions(pair[0], pair[1]),
+                Ordering::Less,
+                "{} should be older than {}",
+                pair[0],
+                pair[1]
+            );
+        }
+    }
+
+    #[test]
+    fn accepts_go_prefix() {
+        assert_eq!(compare_go_versions("go1.22.3", "1.22.3"), Ordering::Equal);
+    }
+
+    #[test]
+    fn language_version_selects_newest_in_family() {
+        let installed = ["1.21.5", "1.22.1", "1.22rc1", "1.22.10", "1.23.0"];
+
+        assert_eq!(select_version("1.22", installed), Some("1.22.10"));
+        assert_eq!(select_version("go1.21", installed), Some("1.21.5"));
+        assert_eq!(select_version("1.24", installed), None);
+    }
+
+    #[test]
+    fn pre_go121_language_version_includes_first_release() {
+        assert_eq!(select_version("1.20", ["1.20", "1.19.3"]), Some("1.20"));
+        assert_eq!(select_version("1.20", ["1.20", "1.20.4"]), Some("1.20.4"));
+        assert_eq!(select_version("1.20", ["1.20rc1", "1.20"]), Some("1.20"));
+        assert_eq!(select_minimum("1.20rc2", ["1.20rc1", "1.20"]), Some("1.20"));
+        assert_eq!(select_version("stable", ["1.19.5", "1.20"]), Some("1.20"));
+    }
+
+    #[test]
+    fn full_version_matches_exactly() {
+        let installed = ["1.22.1", "1.22.3", "1.22rc2"];
+
+        assert_eq!(select_version("1.22.3", installed), Some("1.22.3"));
+        assert_eq!(select_version("go1.22rc2", installed), Some("1.22rc2"));
+        assert_eq!(select_version("1.22.2", installed), None);
+        assert_eq!(select_version("tip", ["tip"]), Some("tip"));
+    }
+
+    #[test]
+    fn minimum_selects_newest_of_family_at_or_above() {
+        let installed = ["1.22.1", "1.22.5", "1.22rc1", "1.23.0"];
+
+        assert_eq!(select_minimum("1.22.3", installed), Some("1.22.5"));
+        assert_eq!(select_minimum("1.22rc2", installed), Some("1.22.5"));
+        assert_eq!(select_minimum("1.22.6", installed), None);
+        assert_eq!(select_minimum("1.22", installed), Some("1.22.5"));
+    }
+
+    #[test]
+    fn latest_and_stable_skip_prereleases() {
+        let installed = ["1.22.3", "1.23rc1", "gopls", "1.21.0"];
+
+        assert_eq!(select_version("stable", installed), Some("1.22.3"));
+        assert_eq!(select_version("latest", installed), Some("1.22.3"));
+    }
+
+    #[test]
+    fn rejects_invalid_versions() {
+        assert!(GoVersion::parse("latest").is_none());
+        assert!(GoVersion::parse("1.21.x").is_none());
+        assert!(GoVersion::parse("1.21gamma1").is_none());
+    }
+}
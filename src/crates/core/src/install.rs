@@ -0,0 +1,413 @@
+//! Downloading and installing Go toolchains into `~/.golta/versions`, shared by
+//! `golta install` and the shim's auto-install.
+
+use crate::gover::{select_version, GoVersion};
+use crate::os_info::get_os_arch_and_format;
+use crate::resolve::versions_dir;
+use crate::versions::{fetch_remote_versions, GoVersionInfo};
+use futures_util::StreamExt;
+use indicatif::{ProgressBar, ProgressStyle};
+use std::error::Error;
+use std::fs;
+#[cfg(windows)]
+use std::fs::File;
+use std::future::Future;
+use std::io::{Cursor, Write};
+use std::path::{Path, PathBuf};
+#[cfg(windows)]
+use zip::ZipArchive;
+
+/// Installs the Go version matching `version_spec` (`latest`/`stable`, `1.22` or `1.22.3`) from go.dev.
+pub async fn install_go_version<W: Write>(
+    version_spec: &str,
+    home: &Path,
+    writer: &mut W,
+) -> Result<(), Box<dyn Error>> {
+    install_go(
+        version_spec,
+        home,
+        fetch_remote_versions,
+        download_with_progress,
+        writer,
+    )
+    .await
+}
+
+/// Same as [`install_go_version`] for callers without an async runtime, such as the shim.
+pub fn install_go_version_blocking<W: Write>(
+    version_spec: &str,
+    home: &Path,
+    writer: &mut W,
+) -> Result<(), Box<dyn Error>> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+    runtime.block_on(install_go_version(version_spec, home, writer))
+}
+
+/// Resolves `version_spec` against `fetch_versions`, then downloads the archive with
+/// `download_bytes` and extracts it into `~/.golta/versions/<version>/go`.
+pub async fn install_go<W, FetchVersions, FetchVersionsFut, DownloadBytes, DownloadBytesFut>(
+    version_spec: &str,
+    home: &Path,
+    fetch_versions: FetchVersions,
+    download_bytes: DownloadBytes,
+    writer: &mut W,
+) -> Result<(), Box<dyn Error>>
+where
+    W: Write,
+    FetchVersions: Fn() -> FetchVersionsFut,
+    FetchVersionsFut: Future<Output = Result<Vec<GoVersionInfo>, Box<dyn Error>>>,
+    DownloadBytes: Fn(String) -> DownloadBytesFut,
+    DownloadBytesFut: Future<Output = Result<Vec<u8>, Box<dyn Error>>>,
+{
+    let version = resolve_go_version(version_spec, fetch_versions, writer).await?;
+
+    writeln!(writer, "Installing Go version {}", version)?;
+
+    let install_dir = build_install_dir(home, &version);
+
+    if install_dir.exists() {
+        writeln!(writer, "Go {} is already installed.", version)?;
+        return Ok(());
+    }
+
+    let (os_arch, archive_format) = get_os_arch_and_format();
+    let url = build_download_url(&version, os_arch, archive_format);
+    writeln!(writer, "Downloading {} ...", url)?;
+
+    let bytes = download_bytes(url).await?;
+
+    fs::create_dir_all(&install_dir)?;
+
+    writeln!(writer, "Extracting...")?;
+    let extract_pb = ProgressBar::new_spinner();
+    extract_pb.set_style(ProgressStyle::with_template(
+        "{spinner:.green} extracting {msg}",
+    )?);
+    #[cfg(target_os = "windows")]
+    {
+        extract_zip(&bytes, &install_dir, &extract_pb)?;
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        extract_tar_gz(&bytes, &install_dir, &extract_pb)?;
+    }
+    extract_pb.finish_with_message("Extracted");
+
+    writeln!(writer, "Go {} installed to {:?}", version, install_dir)?;
+    Ok(())
+}
+
+async fn resolve_go_version<F, Fut>(
+    spec: &str,
+    fetch_versions: F,
+    writer: &mut impl Write,
+) -> Result<String, Box<dyn Error>>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = Result<Vec<GoVersionInfo>, Box<dyn Error>>>,
+{
+    writeln!(writer, "Finding matching Go version for \"{}\"...", spec)?;
+    let remote_versions: Vec<GoVersionInfo> = fetch_versions().await?;
+
+    resolve_go_version_from_list(spec, &remote_versions, writer)
+}
+
+fn resolve_go_version_from_list(
+    spec: &str,
+    versions: &[GoVersionInfo],
+    writer: &mut impl Write,
+) -> Result<String, Box<dyn Error>> {
+    if spec == "latest" || spec == "stable" {
+        let latest_stable = versions
+            .iter()
+            .find(|v| v.stable)
+            .ok_or("Could not find a stable Go version.")?;
+        return Ok(latest_stable.version.trim_start_matches("go").to_string());
+    }
+
+    // A language version such as `1.22` installs the newest stable patch release.
+    let candidates = versions
+        .iter()
+        .filter(|v| v.stable || !is_language_version(spec))
+        .map(|v| v.version.trim_start_matches("go"));
+
+    match select_version(spec, candidates) {
+        Some(version_str) => {
+            writeln!(writer, "Found matching version: {}", version_str).ok();
+            Ok(version_str.to_string())
+        }
+        None => Err(format!(
+            "Go version '{}' not found. See `golta list-remote` for available versions.",
+            spec
+        )
+        .into()),
+    }
+}
+
+fn is_language_version(spec: &str) -> bool {
+    GoVersion::parse(spec).is_some_and(|v| v.is_language_version())
+}
+
+/// Returns the directory a Go version is installed into.
+pub fn build_install_dir(home: &Path, version: &str) -> PathBuf {
+    versions_dir(home).join(version)
+}
+
+fn build_download_url(version: &str, os_arch: &str, archive_format: &str) -> String {
+    format!(
+        "https://golang.org/dl/go{}.{}.{}",
+        version, os_arch, archive_format
+    )
+}
+
+async fn download_with_progress(url: String) -> Result<Vec<u8>, Box<dyn Error>> {
+    let response = reqwest::get(&url).await?.error_for_status()?;
+
+    let total_size = response
+        .content_length()
+        .ok_or_else(|| format!("Failed to get content length from {}", &url))?;
+
+    let pb = ProgressBar::new(total_size);
+    pb.set_style(ProgressStyle::default_bar()
+        .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {bytes}/{total_bytes} ({eta})")?
+        .progress_chars("#>-"));
+
+    let capacity = total_size
+        .try_into()
+        .map_err(|_| "File size is too large to fit in memory on this system.".to_string())?;
+    let mut downloaded_bytes = Vec::with_capacity(capacity);
+    let mut stream = response.bytes_stream();
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk?;
+        downloaded_bytes.extend_from_slice(&chunk);
+        pb.inc(chunk.len() as u64);
+    }
+
+    pb.finish_with_message("Downloaded");
+
+    Ok(downloaded_bytes)
+}
+
+#[cfg(not(target_os = "windows"))]
+fn extract_tar_gz(
+    bytes: &[u8],
+    install_dir: &Path,
+    pb: &ProgressBar,
+) -> Result<(), Box<dyn Error>> {
+    use flate2::read::GzDecoder;
+    use tar::Archive;
+    let tar_gz_cursor = Cursor::new(bytes);
+    let tar = GzDecoder::new(tar_gz_cursor);
+    let mut archive = Archive::new(tar);
+    let temp_extract_dir = install_dir.join("go_temp");
+
+    if !temp_extract_dir.exists() {
+        fs::create_dir_all(&temp_extract_dir)?;
+    }
+
+    archive.set_preserve_permissions(false);
+    archive.set_preserve_mtime(false);
+
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        entry.unpack_in(&temp_extract_dir)?;
+        pb.set_message("...");
+        pb.tick();
+    }
+
+    let source_path = temp_extract_dir.join("go");
+    let destination_path = install_dir.join("go");
+
+    if !source_path.exists() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "Source directory not found",
+        )
+        .into());
+    }
+    if destination_path.exists() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::AlreadyExists,
+            "Destination directory already exists",
+        )
+        .into());
+    }
+
+    fs::rename(temp_extract_dir.join("go"), install_dir.join("go"))?;
+    Ok(())
+}
+
+/// Extracts a byte slice as a zip archive
+#[cfg(windows)]
+fn extract_zip(bytes: &[u8], dest: &Path, pb: &ProgressBar) -> std::io::Result<()> {
+    let cursor = Cursor::new(bytes);
+    let mut zip = ZipArchive::new(cursor)?;
+    let entries = zip.len();
+    pb.set_length(entries as u64);
+    pb.set_style(
+        ProgressStyle::with_template("{spinner:.green} [{pos}/{len}] extracting {msg}")
+            .unwrap_or_else(|_| ProgressStyle::default_spinner()),
+    );
+
+    for i in 0..entries {
+        let mut file = zip.by_index(i)?;
+        let name = file.name().to_string();
+
+        // Extract into the `go/` directory
+        let outpath = dest.join(file.name());
+        if file.name().is_empty() {
+            continue;
+        };
+
+        if file.is_dir() {
+            fs::create_dir_all(&outpath)?;
+        } else {
+            if let Some(p) = outpath.parent() {
+                fs::create_dir_all(p)?;
+            }
+            std::io::copy(&mut file, &mut File::create(outpath)?)?;
+        }
+        pb.set_message(name);
+        pb.inc(1);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_download_url_with_os_and_format() {
+        let url = build_download_url("1.22.3", "linux-amd64", "tar.gz");
+        assert_eq!(url, "https://golang.org/dl/go1.22.3.linux-amd64.tar.gz");
+    }
+
+    #[test]
+    fn resolves_latest_version() {
+        let versions = vec![
+            GoVersionInfo {
+                version: "go1.21.9".into(),
+                stable: false,
+            },
+            GoVersionInfo {
+                version: "go1.22.3".into(),
+                stable: true,
+            },
+        ];
+
+        let mut buffer = Vec::new();
+        let resolved = resolve_go_version_from_list("latest", &versions, &mut buffer).unwrap();
+        assert_eq!(resolved, "1.22.3");
+    }
+
+    #[test]
+    fn resolves_exact_version() {
+        let versions = vec![GoVersionInfo {
+            version: "go1.20.1".into(),
+            stable: true,
+        }];
+
+        let mut buffer = Vec::new();
+        let resolved = resolve_go_version_from_list("1.20.1", &versions, &mut buffer).unwrap();
+        assert_eq!(resolved, "1.20.1");
+    }
+
+    #[test]
+    fn resolves_language_version_to_newest_stable_patch() {
+        let versions = vec![
+            GoVersionInfo {
+                version: "go1.23rc1".into(),
+                stable: false,
+            },
+            GoVersionInfo {
+                version: "go1.22.6".into(),
+                stable: true,
+            },
+            GoVersionInfo {
+                version: "go1.22.5".into(),
+                stable: true,
+            },
+            GoVersionInfo {
+                version: "go1.20".into(),
+                stable: true,
+            },
+        ];
+
+        let mut buffer = Vec::new();
+        let resolved = resolve_go_version_from_list("1.22", &versions, &mut buffer).unwrap();
+        assert_eq!(resolved, "1.22.6");
+        let resolved = resolve_go_version_from_list("1.20", &versions, &mut buffer).unwrap();
+        assert_eq!(resolved, "1.20");
+        assert!(resolve_go_version_from_list("1.23", &versions, &mut buffer).is_err());
+    }
+
+    #[test]
+    fn errors_when_version_not_found() {
+        let versions = vec![GoVersionInfo {
+            version: "go1.20.1".into(),
+            stable: true,
+        }];
+
+        let mut buffer = Vec::new();
+        let err = resolve_go_version_from_list("1.99.0", &versions, &mut buffer).unwrap_err();
+        assert!(err.to_string().contains("not found"));
+    }
+
+    #[test]
+    fn skips_install_if_already_exists() {
+        let home = temp_home();
+        let version = "1.21.0";
+        let install_dir = home.join(".golta").join("versions").join(version);
+        fs::create_dir_all(&install_dir).unwrap();
+
+        let mut buffer = Vec::new();
+
+        let fetcher = || async {
+            Ok(vec![GoVersionInfo {
+                version: format!("go{}", version),
+                stable: true,
+            }])
+        };
+
+        let downloader = |_| async {
+            panic!("Downloader should not be called");
+            #[allow(unreachable_code)]
+            Ok(vec![])
+        };
+
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        rt.block_on(async {
+            install_go(version, &home, fetcher, downloader, &mut buffer)
+                .await
+                .unwrap();
+        });
+
+        let output = String::from_utf8(buffer).unwrap();
+        assert!(output.contains(&format!("Go {} is already installed.", version)));
+
+        fs::remove_dir_all(home).unwrap();
+    }
+
+    fn temp_home() -> PathBuf {
+        let mut path = std::env::temp_dir();
+        let unique = format!(
+            "golta_install_test_{}_{}",
+            std::process::id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        );
+        path.push(unique);
+        path
+    }
+}
@@ -0,0 +1,15 @@
+//! Version resolution and Go installation shared by the `golta` CLI and the `go`
+//! shim, so both binaries agree on which Go version applies to a directory.
+
+pub mod cache;
+pub mod config;
+pub mod go_mod;
+pub mod go_mod_edit;
+pub mod gover;
+pub mod install;
+pub mod os_info;
+pub mod pinned_version;
+pub mod project_file;
+pub mod resolve;
+pub mod system;
+pub mod versions;
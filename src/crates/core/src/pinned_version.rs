@@ -0,0 +1,726 @@
+use crate::go_mod::{parse_go_mod, GoWork};
+use crate::project_file::{ProjectFile, PROJECT_FILE_NAME, SCHEMA_VERSION};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// ファイルシステムへの依存を抽象化してテストで差し替えられるようにする
+pub trait PinFileSystem {
+    fn exists(&self, path: &Path) -> bool;
+    fn read_to_string(&self, path: &Path) -> Result<String, Box<dyn Error>>;
+}
+
+pub(crate) struct StdFs;
+
+impl PinFileSystem for StdFs {
+    fn exists(&self, path: &Path) -> bool {
+        path.exists()
+    }
+
+    fn read_to_string(&self, path: &Path) -> Result<String, Box<dyn Error>> {
+        Ok(fs::read_to_string(path)?)
+    }
+}
+
+/// カレントディレクトリから親方向に Go バージョンの指定を探し、バージョンとパスを返す
+///
+/// 各ディレクトリでは `.golta.json`、`.go-version`、`.tool-versions`、`go.work`／`go.mod` の順に確認し、
+/// より近いディレクトリの指定を優先する。`GOWORK` 環境変数も考慮する
+pub fn find_pinned_go_version() -> Result<Option<(String, PathBuf)>, Box<dyn Error>> {
+    let fs = StdFs;
+    let start = env::current_dir()?;
+    find_pinned_go_version_with(&fs, start.as_path(), &GoWork::from_env())
+}
+
+/// 任意の開始ディレクトリとファイルシステム実装を指定して検索（テスト向け）
+pub fn find_pinned_go_version_from<F: PinFileSystem>(
+    fs: &F,
+    start_dir: &Path,
+) -> Result<Option<(String, PathBuf)>, Box<dyn Error>> {
+    find_pinned_go_version_with(fs, start_dir, &GoWork::Auto)
+}
+
+/// `GOWORK` の設定を指定して検索する
+///
+/// ワークスペース内のモジュールでは go.mod ではなく go.work の `go`／`toolchain` 行が使われる
+pub fn find_pinned_go_version_with<F: PinFileSystem>(
+    fs: &F,
+    start_dir: &Path,
+    gowork: &GoWork,
+) -> Result<Option<(String, PathBuf)>, Box<dyn Error>> {
+    let workspace = match gowork {
+        GoWork::Off => None,
+        GoWork::File(path) => Some(path.clone()),
+        GoWork::Auto => find_go_work_from(fs, start_dir),
+    };
+    let explicit = matches!(gowork, GoWork::File(_));
+
+    let mut current_dir = start_dir.to_path_buf();
+    loop {
+        let pin_file_path = current_dir.join(".golta.json");
+        if fs.exists(&pin_file_path) {
+            let project = read_project_file(fs, &pin_file_path)?;
+            if let Some(go_ver) = project.go {
+                return Ok(Some((go_ver, pin_file_path)));
+            }
+        }
+
+        // goenv / setup-go の `.go-version`
+        let go_version_path = current_dir.join(".go-version");
+        if fs.exists(&go_version_path) {
+            let content = fs.read_to_string(&go_version_path)?;
+            if let Some(go_ver) = extract_go_version_file(&content) {
+                return Ok(Some((go_ver, go_version_path)));
+            }
+        }
+
+        // asdf / mise の `.tool-versions`
+        let tool_versions_path = current_dir.join(".tool-versions");
+        if fs.exists(&tool_versions_path) {
+            let content = fs.read_to_string(&tool_versions_path)?;
+            if let Some(go_ver) = extract_tool_versions(&content) {
+                return Ok(Some((go_ver, tool_versions_path)));
+            }
+        }
+
+        if let Some(found) =
+            find_go_directive_version(fs, &current_dir, workspace.as_deref(), explicit)?
+        {
+            return Ok(Some(found));
+        }
+
+        if !current_dir.pop() {
+            break;
+        }
+    }
+
+    // GOWORK で指定されたワークスペースはモジュールの外でも有効
+    match (&workspace, explicit) {
+        (Some(work), true) => read_go_directive(fs, work),
+        _ => Ok(None),
+    }
+}
+
+/// ディレクトリの go.work／go.mod から `go`／`toolchain` 行のバージョンを読む
+fn find_go_directive_version<F: PinFileSystem>(
+    fs: &F,
+    dir: &Path,
+    workspace: Option<&Path>,
+    explicit: bool,
+) -> Result<Option<(String, PathBuf)>, Box<dyn Error>> {
+    let go_mod_path = dir.join("go.mod");
+    let workspace_here = workspace.is_some_and(|work| work.parent() == Some(dir));
+    if !workspace_here && !fs.exists(&go_mod_path) {
+        return Ok(None);
+    }
+
+    if let Some(work) = workspace {
+        if !workspace_here && !explicit {
+            // 上位のワークスペースに属するモジュールなので go.mod の行は使わない
+            return Ok(None);
+        }
+        if let Some(found) = read_go_directive(fs, work)? {
+            return Ok(Some(found));
+        }
+    }
+    read_go_directive(fs, &go_mod_path)
+}
+
+fn read_go_directive<F: PinFileSystem>(
+    fs: &F,
+    path: &Path,
+) -> Result<Option<(String, PathBuf)>, Box<dyn Error>> {
+    if !fs.exists(path) {
+        return Ok(None);
+    }
+    let content = fs.read_to_string(path)?;
+    Ok(parse_go_mod(&content)
+        .selected_version()
+        .map(|version| (version, path.to_path_buf())))
+}
+
+/// 開始ディレクトリから親方向に go.work を探す
+fn find_go_work_from<F: PinFileSystem>(fs: &F, start_dir: &Path) -> Option<PathBuf> {
+    let mut current_dir = start_dir.to_path_buf();
+    loop {
+        let work = current_dir.join("go.work");
+        if fs.exists(&work) {
+            return Some(work);
+        }
+        if !current_dir.pop() {
+            return None;
+        }
+    }
+}
+
+/// カレントディレクトリから親方向に `.golta.json` の `tools` を探し、ツールのバージョンとパスを返す
+pub fn find_pinned_tool_version(tool: &str) -> Result<Option<(String, PathBuf)>, Box<dyn Error>> {
+    find_pinned_tool_version_in(&env::current_dir()?, tool)
+}
+
+/// 指定したディレクトリから親方向にツールのピンを探す（`tools sync` はモジュールのルートから探す）
+pub fn find_pinned_tool_version_in(
+    start_dir: &Path,
+    tool: &str,
+) -> Result<Option<(String, PathBuf)>, Box<dyn Error>> {
+    find_pinned_tool_version_from(&StdFs, start_dir, tool)
+}
+
+/// 任意の開始ディレクトリとファイルシステム実装を指定してツールのピンを検索（テスト向け）
+pub fn find_pinned_tool_version_from<F: PinFileSystem>(
+    fs: &F,
+    start_dir: &Path,
+    tool: &str,
+) -> Result<Option<(String, PathBuf)>, Box<dyn Error>> {
+    let mut current_dir = start_dir.to_path_buf();
+    loop {
+        let pin_file_path = current_dir.join(".golta.json");
+        if fs.exists(&pin_file_path) {
+            let project = read_project_file(fs, &pin_file_path)?;
+            if let Some(tool_ver) = project.pinned_version(tool) {
+                return Ok(Some((tool_ver.to_string(), pin_file_path)));
+            }
+        }
+
+        if !current_dir.pop() {
+            return Ok(None);
+        }
+    }
+}
+
+/// `.golta.json` の内容に `go`（またはツールなら `tools.<tool>`）を設定し、他のキーは保持したまま返す
+///
+/// 新しく作るファイルには `schema_version` を書き込む
+pub fn set_pinned_version(
+    existing: Option<&str>,
+    tool: &str,
+    version: &str,
+) -> Result<String, Box<dyn Error>> {
+    let mut project = match existing {
+        Some(raw) if !raw.trim().is_empty() => ProjectFile::parse(raw)?,
+        _ => ProjectFile {
+            schema_version: Some(SCHEMA_VERSION),
+            ..ProjectFile::default()
+        },
+    };
+    project.set_pinned_version(tool, version);
+    project.to_json()
+}
+
+/// `.golta.json` から `tool` のピンを取り除いた内容を返す。設定が残らなければ `None`
+pub fn remove_pinned_version(existing: &str, tool: &str) -> Result<Option<String>, Box<dyn Error>> {
+    let mut project = ProjectFile::parse(existing)?;
+    project.remove_pinned_version(tool);
+    if project.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(project.to_json()?))
+}
+
+/// 開始ディレクトリから親方向に、`tool` のピン（`go` なら `go`、それ以外は `tools.<tool>`）を持つ `.golta.json` を探す
+pub fn find_pin_file_from<F: PinFileSystem>(
+    fs: &F,
+    start_dir: &Path,
+    tool: &str,
+) -> Result<Option<PathBuf>, Box<dyn Error>> {
+    let mut current_dir = start_dir.to_path_buf();
+    loop {
+        let pin_file_path = current_dir.join(PROJECT_FILE_NAME);
+        if fs.exists(&pin_file_path)
+            && read_project_file(fs, &pin_file_path)?
+                .pinned_version(tool)
+                .is_some()
+        {
+            return Ok(Some(pin_file_path));
+        }
+
+        if !current_dir.pop() {
+            return Ok(None);
+        }
+    }
+}
+
+/// 開始ディレクトリから親方向に最も近い `.golta.json` を探して読む
+pub fn find_project_file(
+    start_dir: &Path,
+) -> Result<Option<(ProjectFile, PathBuf)>, Box<dyn Error>> {
+    find_project_file_from(&StdFs, start_dir)
+}
+
+/// 任意のファイルシステム実装を指定して最も近い `.golta.json` を探す（テスト向け）
+pub fn find_project_file_from<F: PinFileSystem>(
+    fs: &F,
+    start_dir: &Path,
+) -> Result<Option<(ProjectFile, PathBuf)>, Box<dyn Error>> {
+    let mut current_dir = start_dir.to_path_buf();
+    loop {
+        let pin_file_path = current_dir.join(PROJECT_FILE_NAME);
+        if fs.exists(&pin_file_path) {
+            return Ok(Some((
+                read_project_file(fs, &pin_file_path)?,
+                pin_file_path,
+            )));
+        }
+
+        if !current_dir.pop() {
+            return Ok(None);
+        }
+    }
+}
+
+/// シムが `go` に設定する環境変数と、それを読んだ `.golta.json`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProjectEnv {
+    pub path: PathBuf,
+    pub vars: BTreeMap<String, String>,
+}
+
+/// 最も近い `.golta.json` の `env` を読む。`env` が空なら `None`
+pub fn find_project_env(start_dir: &Path) -> Result<Option<ProjectEnv>, Box<dyn Error>> {
+    find_project_env_from(&StdFs, start_dir)
+}
+
+/// 任意のファイルシステム実装を指定して `env` を読む（テスト向け）
+pub fn find_project_env_from<F: PinFileSystem>(
+    fs: &F,
+    start_dir: &Path,
+) -> Result<Option<ProjectEnv>, Box<dyn Error>> {
+    Ok(find_project_file_from(fs, start_dir)?
+        .filter(|(project, _)| !project.env.is_empty())
+        .map(|(project, path)| ProjectEnv {
+            path,
+            vars: project.env,
+        }))
+}
+
+/// `.golta.json` を読んで解析する。エラーには `path:line:column` の位置を含める
+pub fn read_project_file<F: PinFileSystem>(
+    fs: &F,
+    path: &Path,
+) -> Result<ProjectFile, Box<dyn Error>> {
+    let content = fs.read_to_string(path)?;
+    ProjectFile::parse(&content).map_err(|e| format!("{}:{}", path.display(), e).into())
+}
+
+/// `.go-version` の最初の有効な行を読む（`go1.22.3` のような接頭辞も許容）
+fn extract_go_version_file(content: &str) -> Option<String> {
+    content
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or("").trim())
+        .find(|line| !line.is_empty())
+        .map(|line| line.strip_prefix("go").unwrap_or(line).to_string())
+}
+
+/// `.tool-versions` の `golang`（mise では `go`）の行から最初のバージョンを読む
+fn extract_tool_versions(content: &str) -> Option<String> {
+    content.lines().find_map(|line| {
+        let line = line.split('#').next().unwrap_or("");
+        let mut fields = line.split_whitespace();
+        match fields.next() {
+            Some("golang") | Some("go") => fields.next().map(|v| v.to_string()),
+            _ => None,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+    use std::collections::HashMap;
+
+    struct MockFs {
+        files: HashMap<PathBuf, String>,
+    }
+
+    impl MockFs {
+        fn new(files: HashMap<PathBuf, String>) -> Self {
+            Self { files }
+        }
+    }
+
+    impl PinFileSystem for MockFs {
+        fn exists(&self, path: &Path) -> bool {
+            self.files.contains_key(path)
+        }
+
+        fn read_to_string(&self, path: &Path) -> Result<String, Box<dyn Error>> {
+            self.files
+                .get(path)
+                .cloned()
+                .ok_or_else(|| "missing file".into())
+        }
+    }
+
+    fn pin(path: &str, go: &str) -> (PathBuf, String) {
+        (PathBuf::from(path), format!("{{\"go\":\"{}\"}}", go))
+    }
+
+    #[test]
+    fn finds_in_current_dir() {
+        let start = PathBuf::from("project/sub");
+        let pin_path = PathBuf::from("project/sub/.golta.json");
+        let fs = MockFs::new(HashMap::from([pin(&pin_path.to_string_lossy(), "1.22.0")]));
+
+        let found = find_pinned_go_version_from(&fs, &start).unwrap();
+
+        assert_eq!(
+            found,
+            Some(("1.22.0".to_string(), pin_path)),
+            "should pick current directory pin"
+        );
+    }
+
+    #[test]
+    fn climbs_to_parent_when_missing_locally() {
+        let start = PathBuf::from("project/sub");
+        let parent_pin = PathBuf::from("project/.golta.json");
+        let fs = MockFs::new(HashMap::from([pin(
+            &parent_pin.to_string_lossy(),
+            "1.21.1",
+        )]));
+
+        let found = find_pinned_go_version_from(&fs, &start).unwrap();
+
+        assert_eq!(
+            found,
+            Some(("1.21.1".to_string(), parent_pin)),
+            "should find pin in ancestor"
+        );
+    }
+
+    #[test]
+    fn finds_go_mod_when_pin_missing() {
+        let start = PathBuf::from("project/sub");
+        let go_mod_path = PathBuf::from("project/go.mod");
+        let fs = MockFs::new(HashMap::from([(
+            go_mod_path.clone(),
+            "module example\n\ngo 1.21.5\n".to_string(),
+        )]));
+
+        let found = find_pinned_go_version_from(&fs, &start).unwrap();
+
+        assert_eq!(
+            found,
+            Some(("1.21.5".to_string(), go_mod_path)),
+            "should find version in go.mod"
+        );
+    }
+
+    #[test]
+    fn finds_toolchain_version_in_go_mod() {
+        let start = PathBuf::from("project");
+        let go_mod_path = PathBuf::from("project/go.mod");
+        let content = "module example\n\ngo 1.21\ntoolchain go1.21.5\n";
+        let fs = MockFs::new(HashMap::from([(go_mod_path.clone(), content.to_string())]));
+
+        let found = find_pinned_go_version_from(&fs, &start).unwrap();
+
+        assert_eq!(
+            found,
+            Some(("1.21.5".to_string(), go_mod_path)),
+            "should prefer toolchain version"
+        );
+    }
+
+    #[test]
+    fn returns_none_when_no_pin_anywhere() {
+        let start = PathBuf::from("project/sub");
+        let fs = MockFs::new(HashMap::new());
+
+        let found = find_pinned_go_version_from(&fs, &start).unwrap();
+
+        assert!(found.is_none());
+    }
+
+    #[test]
+    fn ignores_pin_without_go_key_and_continues_search() {
+        let start = PathBuf::from("project/sub");
+        let invalid_pin_path = PathBuf::from("project/sub/.golta.json");
+        let valid_pin_path = PathBuf::from("project/.golta.json");
+        let fs = MockFs::new(HashMap::from([
+            (invalid_pin_path.clone(), "{}".to_string()),
+            pin(&valid_pin_path.to_string_lossy(), "1.20.0"),
+        ]));
+        let found = find_pinned_go_version_from(&fs, &start).unwrap();
+
+        assert_eq!(
+            found,
+            Some(("1.20.0".to_string(), valid_pin_path)),
+            "should skip files without go key"
+        );
+    }
+
+    #[test]
+    fn finds_tool_pin_in_ancestor() {
+        let start = PathBuf::from("project/sub");
+        let pin_path = PathBuf::from("project/.golta.json");
+        let fs = MockFs::new(HashMap::from([(
+            pin_path.clone(),
+            r#"{"go":"1.22.0","tools":{"gopls":"v0.16.1"}}"#.to_string(),
+        )]));
+
+        let found = find_pinned_tool_version_from(&fs, &start, "gopls").unwrap();
+
+        assert_eq!(found, Some(("v0.16.1".to_string(), pin_path)));
+    }
+
+    #[test]
+    fn tool_pin_ignores_go_mod_and_other_tools() {
+        let start = PathBuf::from("project");
+        let fs = MockFs::new(HashMap::from([
+            (
+                PathBuf::from("project/.golta.json"),
+                r#"{"tools":{"dlv":"v1.22.0"}}"#.to_string(),
+            ),
+            (
+                PathBuf::from("project/go.mod"),
+                "module example\n\ngo 1.21.5\n".to_string(),
+            ),
+        ]));
+
+        let found = find_pinned_tool_version_from(&fs, &start, "gopls").unwrap();
+
+        assert!(found.is_none());
+    }
+
+    #[test]
+    fn set_pinned_version_keeps_other_keys() {
+        let existing = r#"{"go":"1.21.0","tools":{"dlv":"v1.22.0"}}"#;
+
+        let updated = set_pinned_version(Some(existing), "gopls", "v0.16.1").unwrap();
+        let updated = set_pinned_version(Some(&updated), "go", "1.22.3").unwrap();
+
+        let json: Value = serde_json::from_str(&updated).unwrap();
+        assert_eq!(json["go"], "1.22.3");
+        assert_eq!(json["tools"]["dlv"], "v1.22.0");
+        assert_eq!(json["tools"]["gopls"], "v0.16.1");
+    }
+
+    #[test]
+    fn set_pinned_version_creates_new_file_contents() {
+        let created = set_pinned_version(None, "go", "1.22.3").unwrap();
+
+        let project = ProjectFile::parse(&created).unwrap();
+        assert_eq!(project.go.as_deref(), Some("1.22.3"));
+        assert_eq!(project.schema_version, Some(SCHEMA_VERSION));
+    }
+
+    #[test]
+    fn finds_pin_file_holding_the_tool() {
+        let start = PathBuf::from("project/sub");
+        let fs = MockFs::new(HashMap::from([
+            (
+                PathBuf::from("project/sub/.golta.json"),
+                r#"{"tools":{"gopls":"v0.16.1"}}"#.to_string(),
+            ),
+            pin("project/.golta.json", "1.22.3"),
+        ]));
+
+        assert_eq!(
+            find_pin_file_from(&fs, &start, "go").unwrap(),
+            Some(PathBuf::from("project/.golta.json"))
+        );
+        assert_eq!(
+            find_pin_file_from(&fs, &start, "gopls").unwrap(),
+            Some(PathBuf::from("project/sub/.golta.json"))
+        );
+        assert!(find_pin_file_from(&fs, &start, "dlv").unwrap().is_none());
+    }
+
+    #[test]
+    fn remove_pinned_version_keeps_other_keys() {
+        let existing = r#"{"go":"1.22.3","tools":{"gopls":"v0.16.1"}}"#;
+
+        let without_go = remove_pinned_version(existing, "go").unwrap().unwrap();
+        let json: Value = serde_json::from_str(&without_go).unwrap();
+        assert!(json.get("go").is_none());
+        assert_eq!(json["tools"]["gopls"], "v0.16.1");
+
+        let without_gopls = remove_pinned_version(existing, "gopls").unwrap().unwrap();
+        let json: Value = serde_json::from_str(&without_gopls).unwrap();
+        assert_eq!(json["go"], "1.22.3");
+        assert!(json.get("tools").is_none());
+
+        assert_eq!(remove_pinned_version(&without_go, "gopls").unwrap(), None);
+    }
+
+    #[test]
+    fn finds_go_version_file() {
+        let start = PathBuf::from("project/sub");
+        let path = PathBuf::from("project/.go-version");
+        let fs = MockFs::new(HashMap::from([(
+            path.clone(),
+            "# goenv\n1.22.3\n".to_string(),
+        )]));
+
+        let found = find_pinned_go_version_from(&fs, &start).unwrap();
+
+        assert_eq!(found, Some(("1.22.3".to_string(), path)));
+    }
+
+    #[test]
+    fn finds_golang_in_tool_versions() {
+        let start = PathBuf::from("project");
+        let path = PathBuf::from("project/.tool-versions");
+        let fs = MockFs::new(HashMap::from([(
+            path.clone(),
+            "nodejs 20.11.0\ngolang 1.21.6 1.20.13 # fallback\n".to_string(),
+        )]));
+
+        let found = find_pinned_go_version_from(&fs, &start).unwrap();
+
+        assert_eq!(found, Some(("1.21.6".to_string(), path)));
+    }
+
+    #[test]
+    fn tool_versions_without_go_is_skipped() {
+        let start = PathBuf::from("project");
+        let go_mod_path = PathBuf::from("project/go.mod");
+        let fs = MockFs::new(HashMap::from([
+            (
+                PathBuf::from("project/.tool-versions"),
+                "nodejs 20.11.0\n".to_string(),
+            ),
+            (go_mod_path.clone(), "module m\n\ngo 1.22.1\n".to_string()),
+        ]));
+
+        let found = find_pinned_go_version_from(&fs, &start).unwrap();
+
+        assert_eq!(found, Some(("1.22.1".to_string(), go_mod_path)));
+    }
+
+    #[test]
+    fn precedence_within_a_directory() {
+        let start = PathBuf::from("project");
+        let files = |names: &[&str]| {
+            let mut files = HashMap::new();
+            for name in names {
+                let content = match *name {
+                    ".golta.json" => r#"{"go":"1.23.0"}"#.to_string(),
+                    ".go-version" => "1.22.0".to_string(),
+                    ".tool-versions" => "golang 1.21.0".to_string(),
+                    _ => "go 1.20.0".to_string(),
+                };
+                files.insert(PathBuf::from("project").join(name), content);
+            }
+            MockFs::new(files)
+        };
+
+        let all = files(&[".golta.json", ".go-version", ".tool-versions", "go.mod"]);
+        let found = find_pinned_go_version_from(&all, &start).unwrap().unwrap();
+        assert_eq!(found.0, "1.23.0");
+
+        let without_pin = files(&[".go-version", ".tool-versions", "go.mod"]);
+        let found = find_pinned_go_version_from(&without_pin, &start)
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.0, "1.22.0");
+
+        let asdf_and_mod = files(&[".tool-versions", "go.mod"]);
+        let found = find_pinned_go_version_from(&asdf_and_mod, &start)
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.0, "1.21.0");
+    }
+
+    #[test]
+    fn nearer_go_mod_beats_parent_go_version_file() {
+        let start = PathBuf::from("project/module");
+        let go_mod_path = PathBuf::from("project/module/go.mod");
+        let fs = MockFs::new(HashMap::from([
+            (PathBuf::from("project/.go-version"), "1.21.0".to_string()),
+            (go_mod_path.clone(), "go 1.22.4".to_string()),
+        ]));
+
+        let found = find_pinned_go_version_from(&fs, &start).unwrap();
+
+        assert_eq!(found, Some(("1.22.4".to_string(), go_mod_path)));
+    }
+
+    fn workspace_fs() -> MockFs {
+        MockFs::new(HashMap::from([
+            (
+                PathBuf::from("ws/go.work"),
+                "go 1.23.0\n\nuse ./api\n".to_string(),
+            ),
+            (
+                PathBuf::from("ws/api/go.mod"),
+                "module api\n\ngo 1.21\n".to_string(),
+            ),
+        ]))
+    }
+
+    #[test]
+    fn workspace_go_work_beats_nested_go_mod() {
+        let found =
+            find_pinned_go_version_with(&workspace_fs(), Path::new("ws/api/pkg"), &GoWork::Auto)
+                .unwrap();
+
+        assert_eq!(
+            found,
+            Some(("1.23.0".to_string(), PathBuf::from("ws/go.work")))
+        );
+    }
+
+    #[test]
+    fn gowork_off_uses_module_go_mod() {
+        let found = find_pinned_go_version_with(&workspace_fs(), Path::new("ws/api"), &GoWork::Off)
+            .unwrap();
+
+        assert_eq!(
+            found,
+            Some(("1.21".to_string(), PathBuf::from("ws/api/go.mod")))
+        );
+    }
+
+    #[test]
+    fn explicit_gowork_applies_outside_modules() {
+        let gowork = GoWork::File(PathBuf::from("ws/go.work"));
+
+        let found =
+            find_pinned_go_version_with(&workspace_fs(), Path::new("elsewhere"), &gowork).unwrap();
+
+        assert_eq!(
+            found,
+            Some(("1.23.0".to_string(), PathBuf::from("ws/go.work")))
+        );
+    }
+
+    #[test]
+    fn project_env_comes_from_nearest_file_only() {
+        let fs = MockFs::new(HashMap::from([
+            (
+                PathBuf::from("project/.golta.json"),
+                r#"{"go":"1.22.3","env":{"CGO_ENABLED":"0"}}"#.to_string(),
+            ),
+            (
+                PathBuf::from("project/tools/.golta.json"),
+                r#"{"tools":{"gopls":"v0.16.1"}}"#.to_string(),
+            ),
+        ]));
+
+        let env = find_project_env_from(&fs, Path::new("project/tools/cmd")).unwrap();
+        assert_eq!(env, None);
+
+        let env = find_project_env_from(&fs, Path::new("project/cmd"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(env.path, PathBuf::from("project/.golta.json"));
+        assert_eq!(env.vars["CGO_ENABLED"], "0");
+    }
+
+    #[test]
+    fn propagates_parse_error_on_invalid_json() {
+        let start = PathBuf::from("project");
+        let pin_path = PathBuf::from("project/.golta.json");
+        let fs = MockFs::new(HashMap::from([(pin_path, "not json".to_string())]));
+
+        let err = find_pinned_go_version_from(&fs, &start).unwrap_err();
+
+        assert_eq!(err.to_string(), "project/.golta.json:1:2: expected ident");
+    }
+}
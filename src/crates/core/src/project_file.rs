@@ -0,0 +1,242 @@
+//! The project file `.golta.json`: the pinned Go version, tool pins and environment
+//! variables, parsed in one place for the CLI and the shim.
+
+use serde::de::{self, Deserializer};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+
+pub const PROJECT_FILE_NAME: &str = ".golta.json";
+
+/// The newest `schema_version` this build understands. Files without one are treated as 1.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Where editors can fetch the JSON Schema of the project file, for the `$schema` key.
+pub const SCHEMA_URL: &str =
+    "https://raw.githubusercontent.com/suwakei/golta/main/schemas/golta.schema.json";
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProjectFile {
+    /// The JSON Schema editors use for completion; golta itself ignores it.
+    #[serde(rename = "$schema", default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "schema_version",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub schema_version: Option<u32>,
+    /// The Go version or spec, such as `1.22.3`, `1.22`, `stable` or `system`.
+    #[serde(
+        default,
+        deserialize_with = "version",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub go: Option<String>,
+    /// Tool versions by tool name, such as `"gopls": "v0.16.1"`.
+    #[serde(
+        default,
+        deserialize_with = "tool_versions",
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub tools: BTreeMap<String, String>,
+    /// Environment variables the shim sets for `go` in this project.
+    #[serde(
+        default,
+        deserialize_with = "env_vars",
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub env: BTreeMap<String, String>,
+}
+
+/// A `.golta.json` that could not be parsed, with the 1-based position of the problem.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectFileError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ProjectFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl Error for ProjectFileError {}
+
+impl ProjectFile {
+    /// Parses the file, rejecting unknown keys (such as a mistyped `"Go"`), values of the
+    /// wrong type and schema versions newer than [`SCHEMA_VERSION`]. An empty file is empty.
+    pub fn parse(content: &str) -> Result<Self, ProjectFileError> {
+        if content.trim().is_empty() {
+            return Ok(Self::default());
+        }
+        serde_json::from_str(content).map_err(|e| {
+            let message = e.to_string();
+            // serde_json appends the position to the message; it is reported separately.
+            let message = match message.rsplit_once(" at line ") {
+                Some((message, _)) => message.to_string(),
+                None => message,
+            };
+            ProjectFileError {
+                line: e.line(),
+                column: e.column(),
+                message,
+            }
+        })
+    }
+
+    /// The pinned version of `tool`: the `go` key for Go, `tools.<tool>` otherwise.
+    pub fn pinned_version(&self, tool: &str) -> Option<&str> {
+        if tool == "go" {
+            self.go.as_deref()
+        } else {
+            self.tools.get(tool).map(String::as_str)
+        }
+    }
+
+    pub fn set_pinned_version(&mut self, tool: &str, version: &str) {
+        if tool == "go" {
+            self.go = Some(version.to_string());
+        } else {
+            self.tools.insert(tool.to_string(), version.to_string());
+        }
+    }
+
+    /// Removes the pin of `tool`, returning whether there was one.
+    pub fn remove_pinned_version(&mut self, tool: &str) -> bool {
+        if tool == "go" {
+            self.go.take().is_some()
+        } else {
+            self.tools.remove(tool).is_some()
+        }
+    }
+
+    /// Whether the file holds no settings, ignoring `$schema` and `schema_version`.
+    pub fn is_empty(&self) -> bool {
+        self.go.is_none() && self.tools.is_empty() && self.env.is_empty()
+    }
+
+    pub fn to_json(&self) -> Result<String, Box<dyn Error>> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+fn schema_version<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<u32>, D::Error> {
+    let version = u32::deserialize(deserializer)?;
+    match version {
+        0 => Err(de::Error::custom("schema_version starts at 1")),
+        v if v > SCHEMA_VERSION => Err(de::Error::custom(format!(
+            "schema_version {} is newer than this golta supports ({}); upgrade golta",
+            v, SCHEMA_VERSION
+        ))),
+        v => Ok(Some(v)),
+    }
+}
+
+fn version<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    let version = String::deserialize(deserializer)?;
+    if version.trim().is_empty() {
+        return Err(de::Error::custom("the Go version must not be empty"));
+    }
+    Ok(Some(version))
+}
+
+fn tool_versions<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<BTreeMap<String, String>, D::Error> {
+    let tools = BTreeMap::<String, String>::deserialize(deserializer)?;
+    if let Some((tool, _)) = tools.iter().find(|(_, version)| version.trim().is_empty()) {
+        return Err(de::Error::custom(format!(
+            "the version of {} must not be empty",
+            tool
+        )));
+    }
+    Ok(tools)
+}
+
+fn env_vars<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<BTreeMap<String, String>, D::Error> {
+    let env = BTreeMap::<String, String>::deserialize(deserializer)?;
+    if let Some(name) = env
+        .keys()
+        .find(|name| name.is_empty() || name.contains(['=', '\0']))
+    {
+        return Err(de::Error::custom(format!(
+            "invalid environment variable name '{}'",
+            name
+        )));
+    }
+    Ok(env)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    #[test]
+    fn parses_all_keys() {
+        let file = ProjectFile::parse(
+            r#"{
+  "schema_version": 1,
+  "go": "1.22",
+  "tools": { "gopls": "v0.16.1" },
+  "env": { "CGO_ENABLED": "0" }
+}"#,
+        )
+        .unwrap();
+
+        assert_eq!(file.pinned_version("go"), Some("1.22"));
+        assert_eq!(file.pinned_version("gopls"), Some("v0.16.1"));
+        assert_eq!(file.env["CGO_ENABLED"], "0");
+    }
+
+    #[test]
+    fn reports_typos_and_wrong_types_with_position() {
+        let err = ProjectFile::parse("{\n  \"Go\": \"1.22.3\"\n}").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 6));
+        assert!(err.message.starts_with("unknown field `Go`"));
+
+        let err = ProjectFile::parse("{\"go\": 1.22}").unwrap_err();
+        assert_eq!(err.line, 1);
+        assert!(err.message.contains("expected a string"));
+
+        let err = ProjectFile::parse("{\"schema_version\": 2}").unwrap_err();
+        assert!(err.to_string().contains("upgrade golta"));
+    }
+
+    #[test]
+    fn round_trips_without_empty_keys() {
+        let mut file = ProjectFile::parse("").unwrap();
+        file.set_pinned_version("go", "1.22.3");
+        file.set_pinned_version("dlv", "v1.22.0");
+        assert!(file.remove_pinned_version("dlv"));
+
+        assert_eq!(file.to_json().unwrap(), "{\n  \"go\": \"1.22.3\"\n}");
+    }
+
+    #[test]
+    fn published_schema_matches_the_keys() {
+        let schema: Value =
+            serde_json::from_str(include_str!("../../../../schemas/golta.schema.json")).unwrap();
+        let mut keys: Vec<&str> = schema["properties"]
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        keys.sort_unstable();
+
+        assert_eq!(keys, ["$schema", "env", "go", "schema_version", "tools"]);
+        assert_eq!(schema["additionalProperties"], false);
+        assert_eq!(
+            schema["properties"]["schema_version"]["maximum"],
+            SCHEMA_VERSION
+        );
+    }
+}
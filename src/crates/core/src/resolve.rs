@@ -0,0 +1,582 @@
+use crate::go_mod::{parse_go_mod, GoWork};
+use crate::gover::{select_minimum, select_version};
+use crate::pinned_version::{find_pinned_go_version_with, PinFileSystem, StdFs};
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The kind of file a Go version was read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VersionSource {
+    /// A session override such as `GOLTA_GO_VERSION` (`golta use`).
+    Environment,
+    /// `.golta.json` in the project.
+    GoltaJson,
+    /// `.go-version` (goenv, setup-go).
+    GoVersionFile,
+    /// `.tool-versions` (asdf, mise).
+    ToolVersions,
+    /// The `toolchain` or `go` directive in go.mod.
+    GoMod,
+    /// The `toolchain` or `go` directive in go.work (or the file named by `GOWORK`).
+    GoWork,
+    /// The global default in `~/.golta/state/default.txt`.
+    Default,
+}
+
+impl VersionSource {
+    fn from_file_name(path: &Path) -> Self {
+        match path.file_name().and_then(|name| name.to_str()) {
+            Some(".golta.json") => Self::GoltaJson,
+            Some(".go-version") => Self::GoVersionFile,
+            Some(".tool-versions") => Self::ToolVersions,
+            Some("go.mod") => Self::GoMod,
+            Some(name) if name.ends_with(".work") => Self::GoWork,
+            _ => Self::Default,
+        }
+    }
+
+    /// Whether a version from this source is a minimum rather than an exact pin:
+    /// `go 1.22.3` in go.mod is satisfied by any installed `1.22.x` at or above it.
+    pub fn is_minimum(self) -> bool {
+        matches!(self, Self::GoMod | Self::GoWork)
+    }
+}
+
+impl fmt::Display for VersionSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Environment => "environment",
+            Self::GoltaJson => ".golta.json",
+            Self::GoVersionFile => ".go-version",
+            Self::ToolVersions => ".tool-versions",
+            Self::GoMod => "go.mod",
+            Self::GoWork => "go.work",
+            Self::Default => "global default",
+        };
+        f.write_str(name)
+    }
+}
+
+/// The Go version that applies to a directory and the file that decided it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Resolution {
+    pub version: String,
+    pub source: VersionSource,
+    /// The file that decided the version; `None` for an environment override.
+    pub path: Option<PathBuf>,
+    /// The 1-based line in `path` holding the version, when it can be located.
+    pub line: Option<usize>,
+}
+
+impl Resolution {
+    /// Describes where the version came from: `go.mod (/src/app/go.mod:3)`.
+    pub fn describe_source(&self) -> String {
+        match (&self.path, self.line) {
+            (Some(path), Some(line)) => format!("{} ({}:{})", self.source, path.display(), line),
+            (Some(path), None) => format!("{} ({})", self.source, path.display()),
+            (None, _) => format!("{} ({})", self.source, session_override_var("go")),
+        }
+    }
+}
+
+impl fmt::Display for Resolution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Go {} from {}", self.version, self.describe_source())
+    }
+}
+
+/// Returns the environment variable that overrides a tool's version for the
+/// current session: `GOLTA_GO_VERSION`, `GOLTA_GOPLS_VERSION`, `GOLTA_GOLANGCI_LINT_VERSION`, ...
+pub fn session_override_var(tool: &str) -> String {
+    format!(
+        "GOLTA_{}_VERSION",
+        tool.to_ascii_uppercase().replace(['-', '.'], "_")
+    )
+}
+
+/// Reads the session override for a tool, ignoring empty values.
+pub fn session_override(tool: &str) -> Option<String> {
+    env::var(session_override_var(tool))
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// The environment variables that take part in resolution.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResolveEnv {
+    /// `GOLTA_GO_VERSION`.
+    pub session_override: Option<String>,
+    /// `GOWORK`.
+    pub gowork: GoWork,
+}
+
+impl ResolveEnv {
+    pub fn from_env() -> Self {
+        Self {
+            session_override: session_override("go"),
+            gowork: GoWork::from_env(),
+        }
+    }
+}
+
+/// Finds the Go version for `start_dir`: a `GOLTA_GO_VERSION` session override, then the
+/// nearest `.golta.json`, `.go-version`, `.tool-versions`, go.work or go.mod (walking up
+/// the tree), then the global default in `~/.golta/state`.
+pub fn find_go_version(
+    start_dir: &Path,
+    home_dir: &Path,
+) -> Result<Option<Resolution>, Box<dyn Error>> {
+    find_go_version_with(&StdFs, &ResolveEnv::from_env(), start_dir, home_dir)
+}
+
+/// Same as [`find_go_version`], with an injectable filesystem and environment for tests.
+pub fn find_go_version_with<F: PinFileSystem>(
+    fs: &F,
+    env: &ResolveEnv,
+    start_dir: &Path,
+    home_dir: &Path,
+) -> Result<Option<Resolution>, Box<dyn Error>> {
+    // 0. A session override beats every file.
+    if let Some(version) = &env.session_override {
+        return Ok(Some(Resolution {
+            version: version.clone(),
+            source: VersionSource::Environment,
+            path: None,
+            line: None,
+        }));
+    }
+
+    // 1. Look for a version pinned to the project.
+    if let Some((version, path)) = find_pinned_go_version_with(fs, start_dir, &env.gowork)? {
+        let source = VersionSource::from_file_name(&path);
+        let line = find_version_line(fs, &path, source);
+        return Ok(Some(Resolution {
+            version,
+            source,
+            path: Some(path),
+            line,
+        }));
+    }
+
+    // 2. If not pinned, look for the global default version.
+    let default_file = default_version_path(home_dir, "go");
+    if let Some(version) = read_version_file(fs, &default_file)? {
+        let line = find_version_line(fs, &default_file, VersionSource::Default);
+        return Ok(Some(Resolution {
+            version,
+            source: VersionSource::Default,
+            path: Some(default_file),
+            line,
+        }));
+    }
+
+    Ok(None)
+}
+
+/// Returns the file holding the global default of a tool
+/// (`default.txt` for Go, `<tool>.default` otherwise).
+pub fn default_version_path(home_dir: &Path, tool: &str) -> PathBuf {
+    let state_dir = home_dir.join(".golta").join("state");
+    if tool == "go" {
+        state_dir.join("default.txt")
+    } else {
+        state_dir.join(format!("{}.default", tool))
+    }
+}
+
+/// Reads the global default version of a tool.
+pub fn read_default_version(home_dir: &Path, tool: &str) -> Result<Option<String>, Box<dyn Error>> {
+    read_version_file(&StdFs, &default_version_path(home_dir, tool))
+}
+
+/// Returns the directory Go toolchains are installed into (`~/.golta/versions`).
+pub fn versions_dir(home_dir: &Path) -> PathBuf {
+    home_dir.join(".golta").join("versions")
+}
+
+/// Lists the installed Go toolchains. Tool directories such as `gopls` are skipped
+/// because they have no `go/` directory.
+pub fn installed_go_versions(home_dir: &Path) -> Result<Vec<String>, Box<dyn Error>> {
+    let versions_dir = versions_dir(home_dir);
+    if !versions_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut versions = Vec::new();
+    for entry in fs::read_dir(&versions_dir)? {
+        let entry = entry?;
+        if !entry.path().join("go").is_dir() {
+            continue;
+        }
+        if let Some(name) = entry.file_name().to_str() {
+            versions.push(name.to_string());
+        }
+    }
+    Ok(versions)
+}
+
+/// Maps a resolved version spec onto an installed toolchain using Go's version
+/// semantics, so `1.22` selects the newest installed `1.22.x`. A full version from
+/// go.mod or go.work is a minimum; from any other source it must match exactly.
+pub fn select_installed_go_version(
+    spec: &str,
+    source: VersionSource,
+    home_dir: &Path,
+) -> Result<Option<String>, Box<dyn Error>> {
+    let spec = spec.trim_start_matches("go@");
+    let installed = installed_go_versions(home_dir)?;
+    let installed = installed.iter().map(String::as_str);
+    let selected = if source.is_minimum() {
+        select_minimum(spec, installed)
+    } else {
+        select_version(spec, installed)
+    };
+    Ok(selected.map(str::to_string))
+}
+
+/// Finds the line of `path` that holds the version, for diagnostics.
+fn find_version_line<F: PinFileSystem>(
+    fs: &F,
+    path: &Path,
+    source: VersionSource,
+) -> Option<usize> {
+    let content = fs.read_to_string(path).ok()?;
+    let mut lines = content.lines().map(str::trim);
+    let position = match source {
+        VersionSource::GoltaJson => return find_top_level_key_line(&content, "go"),
+        VersionSource::ToolVersions => {
+            lines.position(|l| l.starts_with("golang ") || l.starts_with("go "))
+        }
+        VersionSource::GoMod | VersionSource::GoWork => {
+            let selected = parse_go_mod(&content).selected_version()?;
+            let holds = |line: &str, directive: &str| {
+                let mut fields = line.split_whitespace();
+                fields.next() == Some(directive)
+                    && fields.next().map(|v| v.trim_start_matches("go")) == Some(selected.as_str())
+            };
+            let lines: Vec<&str> = lines.collect();
+            lines
+                .iter()
+                .position(|l| holds(l, "toolchain"))
+                .or_else(|| lines.iter().position(|l| holds(l, "go")))
+        }
+        _ => lines.position(|l| !l.is_empty() && !l.starts_with('#')),
+    };
+    position.map(|i| i + 1)
+}
+
+/// Finds the 1-based line of `"key":` in the top-level object of a JSON document, skipping
+/// nested objects and string values that merely contain the same text.
+fn find_top_level_key_line(content: &str, key: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut line = 1;
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\n' => line += 1,
+            '{' | '[' => depth += 1,
+            '}' | ']' => depth = depth.saturating_sub(1),
+            '"' => {
+                let start = line;
+                let mut text = String::new();
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\\' => text.extend(chars.next()),
+                        '\n' => line += 1,
+                        c => text.push(c),
+                    }
+                }
+                if depth != 1 || text != key {
+                    continue;
+                }
+                while let Some(c) = chars.next_if(|c| c.is_whitespace()) {
+                    if c == '\n' {
+                        line += 1;
+                    }
+                }
+                if chars.peek() == Some(&':') {
+                    return Some(start);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+fn read_version_file<F: PinFileSystem>(
+    fs: &F,
+    path: &Path,
+) -> Result<Option<String>, Box<dyn Error>> {
+    if !fs.exists(path) {
+        return Ok(None);
+    }
+    let content = fs.read_to_string(path)?;
+    let version = content.trim();
+    if version.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(version.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    struct MockFs {
+        files: HashMap<PathBuf, String>,
+    }
+
+    impl PinFileSystem for MockFs {
+        fn exists(&self, path: &Path) -> bool {
+            self.files.contains_key(path)
+        }
+
+        fn read_to_string(&self, path: &Path) -> Result<String, Box<dyn Error>> {
+            self.files
+                .get(path)
+                .cloned()
+                .ok_or_else(|| "missing file".into())
+        }
+    }
+
+    fn mock(files: &[(&str, &str)]) -> MockFs {
+        MockFs {
+            files: files
+                .iter()
+                .map(|(p, c)| (PathBuf::from(p), c.to_string()))
+                .collect(),
+        }
+    }
+
+    const HOME: &str = "home";
+    const DEFAULT_FILE: &str = "home/.golta/state/default.txt";
+
+    #[test]
+    fn test_prefers_pinned_version() {
+        let fs = mock(&[
+            ("project/.golta.json", r#"{"go":"1.18.0"}"#),
+            (DEFAULT_FILE, "1.17.0"),
+        ]);
+
+        let found = find_go_version_with(
+            &fs,
+            &ResolveEnv::default(),
+            Path::new("project"),
+            Path::new(HOME),
+        )
+        .unwrap();
+
+        assert_eq!(
+            found,
+            Some(Resolution {
+                version: "1.18.0".to_string(),
+                source: VersionSource::GoltaJson,
+                path: Some(PathBuf::from("project/.golta.json")),
+                line: Some(1),
+            })
+        );
+    }
+
+    #[test]
+    fn test_falls_back_to_default_version() {
+        let fs = mock(&[(DEFAULT_FILE, "1.17.0\n")]);
+
+        let found = find_go_version_with(
+            &fs,
+            &ResolveEnv::default(),
+            Path::new("project"),
+            Path::new(HOME),
+        )
+        .unwrap();
+
+        assert_eq!(
+            found,
+            Some(Resolution {
+                version: "1.17.0".to_string(),
+                source: VersionSource::Default,
+                path: Some(PathBuf::from(DEFAULT_FILE)),
+                line: Some(1),
+            })
+        );
+    }
+
+    #[test]
+    fn locates_top_level_go_key() {
+        let content = "{\n  \"env\": { \"go\": \"x\" },\n  \"tools\": { \"gopls\": \"go\" },\n  \"note\": \"\\\"go\\\"\",\n  \"go\"\n    : \"1.22.3\"\n}";
+
+        assert_eq!(find_top_level_key_line(content, "go"), Some(5));
+        assert_eq!(
+            find_top_level_key_line("{\"env\": {\"go\": \"1\"}}", "go"),
+            None
+        );
+    }
+
+    #[test]
+    fn default_line_skips_leading_blank_lines() {
+        let fs = mock(&[(DEFAULT_FILE, "\n1.17.0\n")]);
+
+        let found = find_go_version_with(
+            &fs,
+            &ResolveEnv::default(),
+            Path::new("project"),
+            Path::new(HOME),
+        )
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(found.line, Some(2));
+    }
+
+    #[test]
+    fn test_returns_none_when_no_version_is_set() {
+        let fs = mock(&[(DEFAULT_FILE, "  ")]);
+
+        let found = find_go_version_with(
+            &fs,
+            &ResolveEnv::default(),
+            Path::new("project"),
+            Path::new(HOME),
+        )
+        .unwrap();
+
+        assert_eq!(found, None);
+    }
+
+    #[test]
+    fn uses_go_mod_toolchain_before_default() {
+        let fs = mock(&[
+            (
+                "project/go.mod",
+                "module m\n\ngo 1.21\ntoolchain go1.21.5\n",
+            ),
+            (DEFAULT_FILE, "1.20.0"),
+        ]);
+
+        let found = find_go_version_with(
+            &fs,
+            &ResolveEnv::default(),
+            Path::new("project/sub"),
+            Path::new(HOME),
+        )
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(found.version, "1.21.5");
+        assert_eq!(found.source, VersionSource::GoMod);
+        assert_eq!(found.path, Some(PathBuf::from("project/go.mod")));
+        assert_eq!(found.line, Some(4));
+    }
+
+    #[test]
+    fn reports_go_work_source() {
+        let fs = mock(&[
+            ("ws/go.work", "go 1.23.1\n"),
+            ("ws/api/go.mod", "module api\ngo 1.21\n"),
+        ]);
+
+        let found = find_go_version_with(
+            &fs,
+            &ResolveEnv::default(),
+            Path::new("ws/api"),
+            Path::new(HOME),
+        )
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(found.version, "1.23.1");
+        assert_eq!(found.source, VersionSource::GoWork);
+    }
+
+    #[test]
+    fn session_override_beats_every_file() {
+        let fs = mock(&[
+            ("project/.golta.json", r#"{"go":"1.18.0"}"#),
+            (DEFAULT_FILE, "1.17.0"),
+        ]);
+        let session = ResolveEnv {
+            session_override: Some("1.21.3".to_string()),
+            ..ResolveEnv::default()
+        };
+
+        let found = find_go_version_with(&fs, &session, Path::new("project"), Path::new(HOME))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(found.version, "1.21.3");
+        assert_eq!(found.source, VersionSource::Environment);
+        assert_eq!(found.path, None);
+    }
+
+    #[test]
+    fn selects_installed_toolchain_for_language_version() {
+        let home = tempfile::tempdir().unwrap();
+        for version in ["1.22.1", "1.22.6", "1.23.0"] {
+            fs::create_dir_all(versions_dir(home.path()).join(version).join("go")).unwrap();
+        }
+        fs::create_dir_all(versions_dir(home.path()).join("gopls").join("v0.16.1")).unwrap();
+
+        let mut installed = installed_go_versions(home.path()).unwrap();
+        installed.sort();
+        assert_eq!(installed, vec!["1.22.1", "1.22.6", "1.23.0"]);
+
+        let selected =
+            select_installed_go_version("1.22", VersionSource::GoMod, home.path()).unwrap();
+        assert_eq!(selected.as_deref(), Some("1.22.6"));
+        let selected =
+            select_installed_go_version("1.21", VersionSource::GoMod, home.path()).unwrap();
+        assert_eq!(selected, None);
+    }
+
+    #[test]
+    fn go_mod_version_is_a_minimum_but_pins_are_exact() {
+        let home = tempfile::tempdir().unwrap();
+        for version in ["1.22.1", "1.22.5", "1.23.0"] {
+            fs::create_dir_all(versions_dir(home.path()).join(version).join("go")).unwrap();
+        }
+
+        for source in [VersionSource::GoMod, VersionSource::GoWork] {
+            let selected = select_installed_go_version("1.22.3", source, home.path()).unwrap();
+            assert_eq!(selected.as_deref(), Some("1.22.5"));
+        }
+        for source in [
+            VersionSource::GoltaJson,
+            VersionSource::GoVersionFile,
+            VersionSource::Default,
+            VersionSource::Environment,
+        ] {
+            let selected = select_installed_go_version("1.22.3", source, home.path()).unwrap();
+            assert_eq!(selected, None);
+        }
+    }
+
+    #[test]
+    fn session_override_var_names() {
+        assert_eq!(session_override_var("go"), "GOLTA_GO_VERSION");
+        assert_eq!(
+            session_override_var("golangci-lint"),
+            "GOLTA_GOLANGCI_LINT_VERSION"
+        );
+    }
+
+    #[test]
+    fn default_path_depends_on_tool() {
+        let home = Path::new(HOME);
+        assert_eq!(
+            default_version_path(home, "go"),
+            PathBuf::from(DEFAULT_FILE)
+        );
+        assert_eq!(
+            default_version_path(home, "gopls"),
+            PathBuf::from("home/.golta/state/gopls.default")
+        );
+    }
+}
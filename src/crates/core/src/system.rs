@@ -0,0 +1,118 @@
+//! The `system` version: a Go toolchain that golta did not install, found on `PATH`.
+
+use std::env;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The version keyword that selects the system Go, usable in pins and defaults.
+pub const SYSTEM_VERSION: &str = "system";
+
+pub fn is_system_version(version: &str) -> bool {
+    version.trim().trim_start_matches("go@") == SYSTEM_VERSION
+}
+
+/// The `go` shim as installed by `golta setup`, `~/.cargo/bin/go`.
+pub fn default_shim_path(home_dir: &Path) -> PathBuf {
+    home_dir
+        .join(".cargo")
+        .join("bin")
+        .join(executable_name("go"))
+}
+
+/// Finds `program` in the directories of `path`, skipping golta's own toolchains under
+/// `~/.golta` and any file that is one of `shims` once symlinks are resolved.
+pub fn find_system_program(
+    program: &str,
+    path: Option<&OsStr>,
+    home_dir: &Path,
+    shims: &[PathBuf],
+) -> Option<PathBuf> {
+    let golta_dir = home_dir.join(".golta");
+    let name = executable_name(program);
+    env::split_paths(path?)
+        .filter(|dir| !dir.starts_with(&golta_dir))
+        .map(|dir| dir.join(&name))
+        .filter(|candidate| is_executable(candidate))
+        .find(|candidate| !shims.iter().any(|shim| is_same_file(candidate, shim)))
+}
+
+/// Whether both paths exist and refer to the same file after resolving symlinks.
+pub fn is_same_file(a: &Path, b: &Path) -> bool {
+    match (fs::canonicalize(a), fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+fn executable_name(program: &str) -> String {
+    if cfg!(windows) {
+        format!("{}.exe", program)
+    } else {
+        program.to_string()
+    }
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    fs::metadata(path).is_ok_and(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::{symlink, PermissionsExt};
+    use tempfile::tempdir;
+
+    fn write_executable(path: &Path) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, "#!/bin/sh\n").unwrap();
+        fs::set_permissions(path, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    #[test]
+    fn skips_the_shim_and_golta_toolchains() {
+        let home = tempdir().unwrap();
+        let shim = home.path().join(".cargo/bin/go");
+        write_executable(&shim);
+        let linked = home.path().join("links/go");
+        fs::create_dir_all(linked.parent().unwrap()).unwrap();
+        symlink(&shim, &linked).unwrap();
+        let golta_go = home.path().join(".golta/versions/1.22.3/go/bin/go");
+        write_executable(&golta_go);
+        let system_go = home.path().join("usr/local/go/bin/go");
+        write_executable(&system_go);
+        let path = env::join_paths([
+            shim.parent().unwrap(),
+            linked.parent().unwrap(),
+            golta_go.parent().unwrap(),
+            system_go.parent().unwrap(),
+        ])
+        .unwrap();
+
+        let found = find_system_program("go", Some(&path), home.path(), &[shim]);
+
+        assert_eq!(found, Some(system_go));
+    }
+
+    #[test]
+    fn ignores_non_executable_files() {
+        let home = tempdir().unwrap();
+        let dir = home.path().join("bin");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("go"), "").unwrap();
+
+        assert_eq!(
+            find_system_program("go", Some(dir.as_os_str()), home.path(), &[]),
+            None
+        );
+        assert!(is_system_version("go@system"));
+        assert!(!is_system_version("1.22.3"));
+    }
+}
@@ -0,0 +1,114 @@
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Explains the shim's decisions on stderr (`GOLTA_LOG=debug` or `GOLTA_EXPLAIN=1`)
+/// and appends them to `~/.golta/logs/shim.log` (`GOLTA_LOG_FILE=1`).
+pub struct Diagnostics {
+    explain: bool,
+    log_file: Option<PathBuf>,
+}
+
+impl Diagnostics {
+    pub fn from_env(home_dir: Option<&Path>) -> Self {
+        let explain = env::var("GOLTA_LOG").is_ok_and(|v| v.eq_ignore_ascii_case("debug"))
+            || is_enabled(env::var("GOLTA_EXPLAIN").ok().as_deref());
+        let log_file = home_dir
+            .filter(|_| is_enabled(env::var("GOLTA_LOG_FILE").ok().as_deref()))
+            .map(log_file_path);
+        Self { explain, log_file }
+    }
+
+    /// Records a decision.
+    pub fn note(&self, message: &str) {
+        if self.explain {
+            eprintln!("golta: {}", message);
+        }
+        self.append("info", message);
+    }
+
+    /// Records an error. It is printed by the caller, so it only goes to the log file.
+    pub fn error(&self, message: &str) {
+        self.append("error", message);
+    }
+
+    fn append(&self, level: &str, message: &str) {
+        let Some(path) = &self.log_file else {
+            return;
+        };
+        // Logging must never break `go`, so failures are ignored.
+        let _ = append_line(path, &format_line(level, message));
+    }
+}
+
+/// Returns the shim log file under `~/.golta/logs`.
+pub fn log_file_path(home_dir: &Path) -> PathBuf {
+    home_dir.join(".golta").join("logs").join("shim.log")
+}
+
+fn is_enabled(value: Option<&str>) -> bool {
+    matches!(value, Some(v) if !v.is_empty() && v != "0" && !v.eq_ignore_ascii_case("false"))
+}
+
+fn format_line(level: &str, message: &str) -> String {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let cwd = env::current_dir()
+        .map(|dir| dir.display().to_string())
+        .unwrap_or_default();
+    format!(
+        "{} pid={} level={} cwd={} {}",
+        timestamp,
+        process::id(),
+        level,
+        cwd,
+        message
+    )
+}
+
+fn append_line(path: &Path, line: &str) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn appends_notes_and_errors_to_log_file() {
+        let home = tempdir().unwrap();
+        let diagnostics = Diagnostics {
+            explain: false,
+            log_file: Some(log_file_path(home.path())),
+        };
+
+        diagnostics.note("Go 1.22.3 from .go-version");
+        diagnostics.error("Go version 1.23.0 is not installed.");
+
+        let log = fs::read_to_string(log_file_path(home.path())).unwrap();
+        let lines: Vec<&str> = log.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains("level=info"));
+        assert!(lines[0].ends_with("Go 1.22.3 from .go-version"));
+        assert!(lines[1].contains("level=error"));
+    }
+
+    #[test]
+    fn enabled_values() {
+        assert!(is_enabled(Some("1")));
+        assert!(is_enabled(Some("true")));
+        assert!(!is_enabled(Some("0")));
+        assert!(!is_enabled(Some("false")));
+        assert!(!is_enabled(None));
+    }
+}
@@ -0,0 +1,138 @@
+use golta_core::config::IsolatedVar;
+use std::collections::BTreeMap;
+use std::env;
+use std::error::Error;
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Builds the variables the shim sets on the child, so that tools it starts (`go generate`,
+/// `go test -exec`, cgo) find the selected toolchain rather than whatever is on `PATH`.
+///
+/// `GOROOT` points at the selected toolchain and its `bin` directory is put first on `PATH`.
+/// Each variable in `isolate` gets a directory of its own for this version; an isolated
+/// `GOBIN` (or `GOPATH/bin`) also goes on `PATH` so binaries installed with it are found.
+pub fn child_env(
+    go_root: &Path,
+    home_dir: &Path,
+    version: &str,
+    isolate: &[IsolatedVar],
+    current_path: Option<&OsStr>,
+) -> Result<Vec<(&'static str, OsString)>, Box<dyn Error>> {
+    let mut vars = vec![("GOROOT", go_root.as_os_str().to_os_string())];
+    let mut prepend = vec![go_root.join("bin")];
+
+    for var in isolate {
+        vars.push((var.name(), var.dir(home_dir, version).into_os_string()));
+    }
+    if isolate.contains(&IsolatedVar::Gobin) {
+        prepend.push(IsolatedVar::Gobin.dir(home_dir, version));
+    } else if isolate.contains(&IsolatedVar::Gopath) {
+        prepend.push(IsolatedVar::Gopath.dir(home_dir, version).join("bin"));
+    }
+
+    // Entries already present are moved to the front rather than repeated, so nested
+    // invocations do not keep growing PATH.
+    let rest: Vec<PathBuf> = current_path
+        .map(|path| env::split_paths(path).collect())
+        .unwrap_or_default();
+    let path = env::join_paths(
+        prepend
+            .iter()
+            .cloned()
+            .chain(rest.into_iter().filter(|dir| !prepend.contains(dir))),
+    )?;
+    vars.push(("PATH", path));
+    Ok(vars)
+}
+
+/// Sets the project's `env` from `.golta.json` on `command`, leaving the variables the shim
+/// already set for the toolchain (`GOROOT`, `PATH`, ...) as they are.
+pub fn apply_project_env(command: &mut Command, project_env: &BTreeMap<String, String>) {
+    let already_set: Vec<OsString> = command
+        .get_envs()
+        .map(|(name, _)| name.to_os_string())
+        .collect();
+    for (name, value) in project_env {
+        if !already_set.iter().any(|set| set == OsStr::new(name)) {
+            command.env(name, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn value<'a>(vars: &'a [(&'static str, OsString)], name: &str) -> Option<&'a OsStr> {
+        vars.iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value.as_os_str())
+    }
+
+    #[test]
+    fn puts_toolchain_bin_first_on_path() {
+        let go_root = Path::new("/h/.golta/versions/1.22.3/go");
+        let current = env::join_paths(["/usr/local/go/bin", "/usr/bin"]).unwrap();
+
+        let vars = child_env(go_root, Path::new("/h"), "1.22.3", &[], Some(&current)).unwrap();
+
+        assert_eq!(value(&vars, "GOROOT"), Some(go_root.as_os_str()));
+        let path: Vec<PathBuf> = env::split_paths(value(&vars, "PATH").unwrap()).collect();
+        assert_eq!(
+            path,
+            vec![
+                go_root.join("bin"),
+                PathBuf::from("/usr/local/go/bin"),
+                PathBuf::from("/usr/bin"),
+            ]
+        );
+        assert_eq!(value(&vars, "GOBIN"), None);
+    }
+
+    #[test]
+    fn isolates_per_version_directories() {
+        let go_root = Path::new("/h/.golta/versions/1.22.3/go");
+        let isolate = [IsolatedVar::Gobin, IsolatedVar::Gocache];
+        // The PATH a nested invocation would see: the toolchain is already first.
+        let current = env::join_paths([go_root.join("bin"), PathBuf::from("/usr/bin")]).unwrap();
+
+        let vars = child_env(go_root, Path::new("/h"), "1.22.3", &isolate, Some(&current)).unwrap();
+
+        let gobin = Path::new("/h/.golta/isolation/1.22.3/bin");
+        assert_eq!(value(&vars, "GOBIN"), Some(gobin.as_os_str()));
+        assert_eq!(
+            value(&vars, "GOCACHE"),
+            Some(Path::new("/h/.golta/isolation/1.22.3/cache").as_os_str())
+        );
+        assert_eq!(value(&vars, "GOPATH"), None);
+        let path: Vec<PathBuf> = env::split_paths(value(&vars, "PATH").unwrap()).collect();
+        assert_eq!(
+            path,
+            vec![
+                go_root.join("bin"),
+                gobin.to_path_buf(),
+                PathBuf::from("/usr/bin")
+            ]
+        );
+    }
+
+    #[test]
+    fn project_env_does_not_override_toolchain_vars() {
+        let mut command = Command::new("go");
+        command.env("GOROOT", "/h/.golta/versions/1.22.3/go");
+        let project_env = BTreeMap::from([
+            ("GOROOT".to_string(), "/elsewhere".to_string()),
+            ("CGO_ENABLED".to_string(), "0".to_string()),
+        ]);
+
+        apply_project_env(&mut command, &project_env);
+
+        let envs: Vec<(&OsStr, Option<&OsStr>)> = command.get_envs().collect();
+        assert!(envs.contains(&(
+            OsStr::new("GOROOT"),
+            Some(OsStr::new("/h/.golta/versions/1.22.3/go"))
+        )));
+        assert!(envs.contains(&(OsStr::new("CGO_ENABLED"), Some(OsStr::new("0")))));
+    }
+}
@@ -1,168 +1,271 @@
+mod diagnostics;
+mod environment;
+mod prompt;
+mod toolchain;
+
+use diagnostics::Diagnostics;
+use golta_core::cache::ShimLookup;
+use golta_core::config::{config_path, load_config, save_config, GoltaConfig, AUTO_INSTALL_ENV};
+use golta_core::install::install_go_version_blocking;
+use golta_core::resolve::VersionSource;
+use golta_core::system::{
+    default_shim_path, find_system_program, is_same_file, is_system_version, SYSTEM_VERSION,
+};
+use golta_core::{cache, pinned_version, resolve};
+use prompt::{Decision, Terminal, Tty};
 use std::env;
 use std::error::Error;
 use std::ffi::OsString;
-use std::fs;
-use std::io::{self, Write};
+use std::io;
 #[cfg(unix)]
 use std::os::unix::process::CommandExt;
 use std::path::{Path, PathBuf};
 use std::process::{exit, Command};
+use toolchain::ToolchainPlan;
 
 fn main() {
+    let diagnostics = Diagnostics::from_env(home::home_dir().as_deref());
+
     // If an error occurs in the main function, print it to stderr and exit.
-    if let Err(e) = run() {
+    if let Err(e) = run(&diagnostics) {
+        diagnostics.error(&e.to_string());
         eprintln!("golta-shim error: {}", e);
         exit(1);
     }
 }
 
-fn run() -> Result<(), Box<dyn Error>> {
+/// Counts nested shim invocations, so a shim that ends up running itself stops instead of
+/// exec'ing in a loop.
+const DEPTH_ENV: &str = "GOLTA_SHIM_DEPTH";
+const MAX_DEPTH: u32 = 8;
+
+const NO_VERSION_ERROR: &str = "No Go version is set. Use `golta pin go@<version>` in your project, or `golta default go@<version>` globally.";
+
+fn run(diagnostics: &Diagnostics) -> Result<(), Box<dyn Error>> {
+    let depth = shim_depth(env::var(DEPTH_ENV).ok().as_deref())?;
     let current_dir = env::current_dir()?;
     let home_dir = home::home_dir().ok_or("Could not find home directory")?;
+    let shims = shim_paths(&home_dir);
+
+    // 1. Determine the Go version to use. An explicit GOTOOLCHAIN such as `go1.22.3` wins.
+    let toolchain = ToolchainPlan::from_env(&home_dir);
+    let (version, source, project_env) = match toolchain.version_override() {
+        Some(version) => {
+            diagnostics.note(&format!("Go {} from GOTOOLCHAIN", version));
+            let project_env =
+                pinned_version::find_project_env(&current_dir).map_err(|e| e.to_string());
+            (version.to_string(), VersionSource::Environment, project_env)
+        }
+        None => {
+            let lookup = lookup_project(&current_dir, &home_dir)?;
+            let (version, source) = match lookup.resolution {
+                Some(resolution) => {
+                    diagnostics.note(&resolution.to_string());
+                    (resolution.version, resolution.source)
+                }
+                // Without any golta configuration, behave as if golta were not installed.
+                None if find_system_go("go", &home_dir, &shims).is_some() => {
+                    diagnostics.note("no Go version is set; falling back to the system Go");
+                    (SYSTEM_VERSION.to_string(), VersionSource::Default)
+                }
+                None => return Err(NO_VERSION_ERROR.into()),
+            };
+            (version, source, lookup.env)
+        }
+    };
 
-    // 1. Determine the Go version to use.
-    let version = find_go_version(&current_dir, &home_dir)?;
+    // 2. Get the invoked name (`go`, `gofmt`, ...) and arguments for the child process.
+    let mut argv = env::args_os();
+    let program = program_name(argv.next().as_deref());
+    let args: Vec<OsString> = argv.collect();
 
-    // 2. Get arguments for the child process.
-    let args: Vec<OsString> = env::args_os().skip(1).collect();
+    // 3. Build the command for the selected toolchain.
+    let mut command = if is_system_version(&version) {
+        system_command(&program, &home_dir, &shims, diagnostics)?
+    } else {
+        managed_command(
+            &program,
+            &version,
+            source,
+            &home_dir,
+            &shims,
+            &toolchain,
+            diagnostics,
+        )?
+    };
+    command.args(args);
+    command.env(DEPTH_ENV, (depth + 1).to_string());
+    match project_env {
+        Ok(Some(project_env)) => {
+            diagnostics.note(&format!("environment from {}", project_env.path.display()));
+            environment::apply_project_env(&mut command, &project_env.vars);
+        }
+        Ok(None) => {}
+        Err(e) => eprintln!("golta: warning: not applying the project env: {}", e),
+    }
 
-    // 3. Execute the command and get the exit code.
-    let exit_code = execute_go(&version, &home_dir, args)?;
+    // 4. Execute the command and get the exit code.
+    let exit_code = execute_go(command)?;
 
     exit(exit_code);
 }
 
-/// Finds the active Go version by searching for `.golta.json` and then checking the global default.
-fn find_go_version(start_dir: &Path, home_dir: &Path) -> Result<String, Box<dyn Error>> {
-    // 1a. Look for a version pinned in the project, traversing up from start_dir.
-    let mut current_dir = start_dir.to_path_buf();
-    loop {
-        let pin_file_path = current_dir.join(".golta.json");
-        if pin_file_path.exists() {
-            let content = fs::read_to_string(pin_file_path)?;
-            let json: serde_json::Value = serde_json::from_str(&content)?;
-            if let Some(go_ver) = json.get("go").and_then(|v| v.as_str()) {
-                return Ok(go_ver.to_string());
-            }
-        }
-
-        // 1b. Look for a version pinned in go.mod
-        let go_mod_path = current_dir.join("go.mod");
-        if go_mod_path.exists() {
-            let content = fs::read_to_string(go_mod_path)?;
-            let mut go_version = None;
-            let mut toolchain_version = None;
-
-            for line in content.lines() {
-                // Strip comments and whitespace.
-                let line = line.split("//").next().unwrap_or("").trim();
-                if line.is_empty() {
-                    continue;
-                }
-
-                let parts: Vec<&str> = line.split_whitespace().collect();
-                if parts.len() < 2 {
-                    continue;
-                }
-
-                match parts[0] {
-                    "go" => go_version = Some(parts[1].to_string()),
-                    "toolchain" => {
-                        // toolchain usually looks like "go1.21.5", so we strip "go".
-                        let v = parts[1].strip_prefix("go").unwrap_or(parts[1]);
-                        toolchain_version = Some(v.to_string());
-                    }
-                    _ => {}
-                }
-            }
+/// Finds the active Go version using the resolution shared with the `golta` CLI, and the
+/// project `env`, reusing the cached result while the files they came from are unchanged.
+fn lookup_project(start_dir: &Path, home_dir: &Path) -> Result<ShimLookup, Box<dyn Error>> {
+    cache::find_shim_lookup_cached(start_dir, home_dir)
+}
 
-            // The toolchain directive takes precedence over the go directive.
-            if let Some(v) = toolchain_version.or(go_version) {
-                return Ok(v);
-            }
-        }
+/// Parses the nesting depth inherited from a parent shim and refuses to go deeper than
+/// `MAX_DEPTH`.
+fn shim_depth(value: Option<&str>) -> Result<u32, Box<dyn Error>> {
+    let depth = value.and_then(|v| v.trim().parse().ok()).unwrap_or(0);
+    if depth >= MAX_DEPTH {
+        return Err(format!(
+            "The go shim has called itself {} times in a row. Check that PATH and the selected Go version do not lead back to the shim.",
+            depth
+        )
+        .into());
+    }
+    Ok(depth)
+}
 
-        if !current_dir.pop() {
-            break; // Reached the root directory.
-        }
+/// The files that are this shim: the running binary and the one `golta setup` installs.
+fn shim_paths(home_dir: &Path) -> Vec<PathBuf> {
+    let mut shims = vec![default_shim_path(home_dir)];
+    if let Ok(exe) = env::current_exe() {
+        shims.push(exe);
     }
+    shims
+}
 
-    // 1b. If not pinned, read the global default version.
-    let default_file = home_dir.join(".golta").join("state").join("default.txt");
-    let version = fs::read_to_string(default_file).unwrap_or_default();
-    let version = version.trim().to_string();
+fn find_system_go(program: &str, home_dir: &Path, shims: &[PathBuf]) -> Option<PathBuf> {
+    find_system_program(program, env::var_os("PATH").as_deref(), home_dir, shims)
+}
 
-    if version.is_empty() {
-        Err("No Go version is set. Use `golta pin go@<version>` in your project, or `golta default go@<version>` globally.".into())
-    } else {
-        Ok(version)
+/// Returns the toolchain binary this shim was invoked as. The same binary is linked as
+/// `go`, `gofmt`, ... and runs the same-named binary from the selected `go/bin`.
+fn program_name(argv0: Option<&std::ffi::OsStr>) -> String {
+    argv0
+        .map(Path::new)
+        .and_then(|path| path.file_stem())
+        .and_then(|stem| stem.to_str())
+        .filter(|stem| !stem.is_empty() && *stem != "golta-shim")
+        .unwrap_or("go")
+        .to_string()
+}
+
+/// Runs `program` from the next Go on PATH that is not golta's, leaving its environment
+/// (GOROOT, GOTOOLCHAIN) to the user as if golta were not installed.
+fn system_command(
+    program: &str,
+    home_dir: &Path,
+    shims: &[PathBuf],
+    diagnostics: &Diagnostics,
+) -> Result<Command, Box<dyn Error>> {
+    let system_go = find_system_go(program, home_dir, shims).ok_or_else(|| {
+        format!(
+            "The system Go is selected, but no `{}` other than the golta shim was found on PATH.",
+            program
+        )
+    })?;
+    diagnostics.note(&format!("running system {}", system_go.display()));
+
+    let mut command = Command::new(&system_go);
+    // Nested `go` calls should reach the same binary without going through the shim again.
+    if let Some(bin_dir) = system_go.parent() {
+        let rest = env::var_os("PATH").unwrap_or_default();
+        let path =
+            env::join_paths(std::iter::once(bin_dir.to_path_buf()).chain(env::split_paths(&rest)))?;
+        command.env("PATH", path);
     }
+    Ok(command)
 }
 
-/// Constructs the path to the Go executable and runs it.
-fn execute_go(version: &str, home_dir: &Path, args: Vec<OsString>) -> Result<i32, Box<dyn Error>> {
-    let go_executable_name = if cfg!(windows) { "go.exe" } else { "go" };
+/// Picks the installed toolchain for the resolved version (installing it if needed) and
+/// prepares `program` from its `go/bin`.
+fn managed_command(
+    program: &str,
+    version: &str,
+    source: VersionSource,
+    home_dir: &Path,
+    shims: &[PathBuf],
+    toolchain: &ToolchainPlan,
+    diagnostics: &Diagnostics,
+) -> Result<Command, Box<dyn Error>> {
     let version_number = version.trim_start_matches("go@");
-    let real_go_path: PathBuf = home_dir
-        .join(".golta")
-        .join("versions")
-        .join(version_number)
-        .join("go")
-        .join("bin")
-        .join(go_executable_name);
 
+    // `go 1.22.3` in go.mod accepts any installed 1.22.x at or above it, so only install
+    // when nothing matches.
+    let installed = match resolve::select_installed_go_version(version_number, source, home_dir)? {
+        Some(installed) => installed,
+        None => {
+            install_missing_go(version_number, home_dir)?;
+            resolve::select_installed_go_version(version_number, source, home_dir)?
+                .ok_or_else(|| format!("Go version {} is still not installed.", version_number))?
+        }
+    };
+    let real_go_path = go_executable_path(home_dir, &installed, program);
     if !real_go_path.exists() {
-        // Check for CI environment or explicit auto-install flag to avoid blocking.
-        let is_ci = env::var("CI").is_ok();
-        let auto_install = env::var("GOLTA_AUTO_INSTALL")
-            .map(|v| v == "1" || v == "true")
-            .unwrap_or(false);
+        return Err(format!("`{}` is not part of Go {}.", program, installed).into());
+    }
+    if shims.iter().any(|shim| is_same_file(&real_go_path, shim)) {
+        return Err(format!(
+            "{} is the golta shim itself. Reinstall Go {} with `golta install go@{}`.",
+            real_go_path.display(),
+            installed,
+            installed
+        )
+        .into());
+    }
+    diagnostics.note(&format!("running {}", real_go_path.display()));
 
-        if is_ci && !auto_install {
-            return Err(format!(
-                "Go version {} is not installed. CI detected, aborting.",
-                version_number
-            )
-            .into());
-        }
+    let mut command = Command::new(&real_go_path);
 
-        let should_install = if auto_install {
-            true
-        } else {
-            eprintln!("Go version {} is not installed.", version_number);
-            eprint!("Would you like to install it? [Y/n] ");
-            io::stderr().flush()?;
-
-            let mut input = String::new();
-            io::stdin().read_line(&mut input)?;
-            let input = input.trim().to_lowercase();
-            input.is_empty() || input == "y" || input == "yes"
-        };
-
-        if should_install {
-            let status = Command::new("golta")
-                .arg("install")
-                .arg(format!("go@{}", version_number))
-                .status()?;
-
-            if !status.success() {
-                return Err(format!("Failed to install Go version {}.", version).into());
-            }
-        } else {
-            return Err(format!("Go version {} is not installed.", version).into());
+    // Run the child inside the selected toolchain: GOROOT, its bin first on PATH, and any
+    // per-version GOBIN/GOPATH/GOCACHE from the config.
+    let go_root = go_root_path(home_dir, &installed);
+    let config = load_config_or_warn(home_dir).unwrap_or_default();
+    let path = env::var_os("PATH");
+    for (name, value) in environment::child_env(
+        &go_root,
+        home_dir,
+        &installed,
+        &config.isolate,
+        path.as_deref(),
+    )? {
+        if name != "PATH" {
+            diagnostics.note(&format!(
+                "{}={} for the child",
+                name,
+                value.to_string_lossy()
+            ));
         }
+        command.env(name, value);
     }
 
-    let mut command = Command::new(&real_go_path);
-    command.args(args);
+    // Keep Go from switching to a module-cache toolchain unless the user opted in.
+    if let Some(value) = toolchain.child_value() {
+        diagnostics.note(&format!("GOTOOLCHAIN={} for the child", value));
+        command.env("GOTOOLCHAIN", value);
+    }
+    Ok(command)
+}
 
-    // Set GOROOT to ensure the correct toolchain is used, avoiding conflicts with system GOROOT.
-    if let Some(bin_dir) = real_go_path.parent() {
-        if let Some(go_root) = bin_dir.parent() {
-            command.env("GOROOT", go_root);
+/// Reads `~/.golta/config.json`, or warns and returns `None` when it is invalid, so a
+/// hand-edited file does not break every `go` command.
+fn load_config_or_warn(home_dir: &Path) -> Option<GoltaConfig> {
+    match load_config(home_dir) {
+        Ok(config) => Some(config),
+        Err(e) => {
+            eprintln!("golta: warning: {}; using the default settings.", e);
+            None
         }
     }
+}
 
+/// Replaces the shim with `command` (or waits for it where `exec` is unavailable).
+fn execute_go(mut command: Command) -> Result<i32, Box<dyn Error>> {
     #[cfg(unix)]
     {
         let err = command.exec();
@@ -176,23 +279,128 @@ fn execute_go(version: &str, home_dir: &Path, args: Vec<OsString>) -> Result<i32
     }
 }
 
+/// Installs a missing Go version according to the auto-install policy, asking on the
+/// terminal when it is `prompt`. Like `golta install`, a language version such as `1.22`
+/// installs the newest stable patch release.
+fn install_missing_go(version_number: &str, home_dir: &Path) -> Result<(), Box<dyn Error>> {
+    let loaded = load_config_or_warn(home_dir);
+    let config_is_valid = loaded.is_some();
+    let mut config = loaded.unwrap_or_default();
+    let policy = config.auto_install_policy(env::var(AUTO_INSTALL_ENV).ok().as_deref())?;
+
+    let mut tty = Tty::open();
+    let terminal = tty.as_mut().map(|tty| tty as &mut dyn Terminal);
+    let (decision, remember) = prompt::decide(version_number, policy, terminal)?;
+
+    if let Some(policy) = remember {
+        // Saving over an invalid file would throw away whatever the user wrote in it.
+        if config_is_valid {
+            config.auto_install = Some(policy);
+            save_config(home_dir, &config)?;
+            eprintln!(
+                "Saved auto_install = \"{}\" to {}. Change it with `golta config auto-install <never|prompt|always>`, or override it for one run with {}.",
+                policy,
+                config_path(home_dir).display(),
+                AUTO_INSTALL_ENV
+            );
+        } else {
+            eprintln!(
+                "Not saving auto_install = \"{}\" because {} is invalid.",
+                policy,
+                config_path(home_dir).display()
+            );
+        }
+    }
+
+    match decision {
+        Decision::Install => {}
+        Decision::Decline => {
+            return Err(format!(
+                "Go version {} is not installed. Run `golta install go@{}`.",
+                version_number, version_number
+            )
+            .into())
+        }
+        Decision::CannotAsk => {
+            return Err(format!(
+                "Go version {} is not installed and there is no terminal to ask on. Run `golta install go@{}`, or set GOLTA_AUTO_INSTALL=always.",
+                version_number, version_number
+            )
+            .into())
+        }
+    }
+
+    // Install in-process with the same code as `golta install`, so `golta` need not be on PATH.
+    // Progress goes to stderr so it never mixes with the program's own output.
+    install_go_version_blocking(version_number, home_dir, &mut io::stderr())
+        .map_err(|e| format!("Failed to install Go version {}: {}", version_number, e).into())
+}
+
+fn go_executable_path(home_dir: &Path, version: &str, program: &str) -> PathBuf {
+    let go_executable_name = if cfg!(windows) {
+        format!("{}.exe", program)
+    } else {
+        program.to_string()
+    };
+    go_root_path(home_dir, version)
+        .join("bin")
+        .join(go_executable_name)
+}
+
+fn go_root_path(home_dir: &Path, version: &str) -> PathBuf {
+    home_dir
+        .join(".golta")
+        .join("versions")
+        .join(version)
+        .join("go")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
     use tempfile::tempdir;
 
+    #[test]
+    fn test_program_name_from_argv0() {
+        assert_eq!(
+            program_name(Some("/home/u/.cargo/bin/gofmt".as_ref())),
+            "gofmt"
+        );
+        assert_eq!(program_name(Some("go".as_ref())), "go");
+        assert_eq!(program_name(Some("gofmt.exe".as_ref())), "gofmt");
+        assert_eq!(program_name(None), "go");
+    }
+
+    #[test]
+    fn test_shim_depth_guard() {
+        assert_eq!(shim_depth(None).unwrap(), 0);
+        assert_eq!(shim_depth(Some("3")).unwrap(), 3);
+        assert_eq!(shim_depth(Some("junk")).unwrap(), 0);
+        assert!(shim_depth(Some("8")).is_err());
+    }
+
+    #[test]
+    fn invalid_config_falls_back_to_defaults() {
+        let home_dir = tempdir().unwrap();
+        assert!(load_config_or_warn(home_dir.path()).is_some());
+
+        let path = config_path(home_dir.path());
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "{\"isolate\": [\"GOBIN\",]}").unwrap();
+
+        assert!(load_config_or_warn(home_dir.path()).is_none());
+    }
+
     #[test]
     fn test_find_go_version_no_config() {
         let project_dir = tempdir().unwrap();
         let home_dir = tempdir().unwrap();
 
-        let result = find_go_version(project_dir.path(), home_dir.path());
+        let result =
+            lookup_project(project_dir.path(), home_dir.path()).map(|lookup| lookup.resolution);
 
-        assert!(result.is_err());
-        assert_eq!(
-            result.unwrap_err().to_string(),
-            "No Go version is set. Use `golta pin go@<version>` in your project, or `golta default go@<version>` globally."
-        );
+        assert!(result.unwrap().is_none());
     }
 
     #[test]
@@ -202,10 +410,10 @@ mod tests {
         let pin_file = project_dir.path().join(".golta.json");
         fs::write(pin_file, r#"{"go": "1.21.0"}"#).unwrap();
 
-        let result = find_go_version(project_dir.path(), home_dir.path());
+        let result =
+            lookup_project(project_dir.path(), home_dir.path()).map(|lookup| lookup.resolution);
 
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), "1.21.0");
+        assert_eq!(result.unwrap().unwrap().version, "1.21.0");
     }
 
     #[test]
@@ -217,10 +425,9 @@ mod tests {
         let pin_file = project_dir.path().join(".golta.json");
         fs::write(pin_file, r#"{"go": "1.22.0"}"#).unwrap();
 
-        let result = find_go_version(&sub_dir, home_dir.path());
+        let result = lookup_project(&sub_dir, home_dir.path()).map(|lookup| lookup.resolution);
 
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), "1.22.0");
+        assert_eq!(result.unwrap().unwrap().version, "1.22.0");
     }
 
     #[test]
@@ -231,10 +438,10 @@ mod tests {
         fs::create_dir_all(&state_dir).unwrap();
         fs::write(state_dir.join("default.txt"), "1.20.5").unwrap();
 
-        let result = find_go_version(project_dir.path(), home_dir.path());
+        let result =
+            lookup_project(project_dir.path(), home_dir.path()).map(|lookup| lookup.resolution);
 
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), "1.20.5");
+        assert_eq!(result.unwrap().unwrap().version, "1.20.5");
     }
 
     #[test]
@@ -248,10 +455,10 @@ mod tests {
         fs::create_dir_all(&state_dir).unwrap();
         fs::write(state_dir.join("default.txt"), "1.20.5").unwrap();
 
-        let result = find_go_version(project_dir.path(), home_dir.path());
+        let result =
+            lookup_project(project_dir.path(), home_dir.path()).map(|lookup| lookup.resolution);
 
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), "1.21.0");
+        assert_eq!(result.unwrap().unwrap().version, "1.21.0");
     }
 
     #[test]
@@ -261,10 +468,10 @@ mod tests {
         let go_mod = project_dir.path().join("go.mod");
         fs::write(go_mod, "module example.com/test\n\ngo 1.23.0\n").unwrap();
 
-        let result = find_go_version(project_dir.path(), home_dir.path());
+        let result =
+            lookup_project(project_dir.path(), home_dir.path()).map(|lookup| lookup.resolution);
 
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), "1.23.0");
+        assert_eq!(result.unwrap().unwrap().version, "1.23.0");
     }
 
     #[test]
@@ -278,10 +485,10 @@ mod tests {
         )
         .unwrap();
 
-        let result = find_go_version(project_dir.path(), home_dir.path());
+        let result =
+            lookup_project(project_dir.path(), home_dir.path()).map(|lookup| lookup.resolution);
 
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), "1.23.0");
+        assert_eq!(result.unwrap().unwrap().version, "1.23.0");
     }
 
     #[test]
@@ -295,9 +502,38 @@ mod tests {
         )
         .unwrap();
 
-        let result = find_go_version(project_dir.path(), home_dir.path());
+        let result =
+            lookup_project(project_dir.path(), home_dir.path()).map(|lookup| lookup.resolution);
+
+        assert_eq!(result.unwrap().unwrap().version, "1.21.5");
+    }
+
+    #[test]
+    fn test_find_go_version_with_go_version_file() {
+        let project_dir = tempdir().unwrap();
+        let home_dir = tempdir().unwrap();
+        fs::write(project_dir.path().join(".go-version"), "1.22.3\n").unwrap();
+        fs::write(
+            project_dir.path().join("go.mod"),
+            "module example.com/test\n\ngo 1.21.0\n",
+        )
+        .unwrap();
+
+        let result =
+            lookup_project(project_dir.path(), home_dir.path()).map(|lookup| lookup.resolution);
+
+        assert_eq!(result.unwrap().unwrap().version, "1.22.3");
+    }
+
+    #[test]
+    fn test_find_go_version_with_tool_versions() {
+        let project_dir = tempdir().unwrap();
+        let home_dir = tempdir().unwrap();
+        fs::write(project_dir.path().join(".tool-versions"), "golang 1.21.6\n").unwrap();
+
+        let result =
+            lookup_project(project_dir.path(), home_dir.path()).map(|lookup| lookup.resolution);
 
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), "1.21.5");
+        assert_eq!(result.unwrap().unwrap().version, "1.21.6");
     }
 }
@@ -0,0 +1,143 @@
+use golta_core::config::AutoInstall;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, IsTerminal, Write};
+
+/// Asks the user a question and returns the raw answer line.
+pub trait Terminal {
+    fn ask(&mut self, question: &str) -> io::Result<String>;
+}
+
+/// The controlling terminal. Answers are read from `/dev/tty` (`CONIN$` on Windows), never
+/// from stdin, so input piped into `go run` is left for the program.
+pub struct Tty {
+    reader: BufReader<File>,
+}
+
+impl Tty {
+    /// Opens the terminal, or returns `None` when the shim is not run interactively.
+    pub fn open() -> Option<Self> {
+        if !io::stderr().is_terminal() {
+            return None;
+        }
+        let path = if cfg!(windows) { "CONIN$" } else { "/dev/tty" };
+        let file = File::open(path).ok()?;
+        Some(Self {
+            reader: BufReader::new(file),
+        })
+    }
+}
+
+impl Terminal for Tty {
+    fn ask(&mut self, question: &str) -> io::Result<String> {
+        eprint!("{}", question);
+        io::stderr().flush()?;
+        let mut input = String::new();
+        self.reader.read_line(&mut input)?;
+        Ok(input)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decision {
+    Install,
+    Decline,
+    /// The policy is `prompt`, but there is no terminal to ask on.
+    CannotAsk,
+}
+
+/// Decides whether to install a missing Go version under `policy`, asking on `terminal`
+/// when the policy is `prompt`. Also returns the policy to remember if the user chose
+/// `always` or `never`.
+pub fn decide(
+    version: &str,
+    policy: AutoInstall,
+    terminal: Option<&mut dyn Terminal>,
+) -> io::Result<(Decision, Option<AutoInstall>)> {
+    match policy {
+        AutoInstall::Always => return Ok((Decision::Install, None)),
+        AutoInstall::Never => return Ok((Decision::Decline, None)),
+        AutoInstall::Prompt => {}
+    }
+    let Some(terminal) = terminal else {
+        return Ok((Decision::CannotAsk, None));
+    };
+
+    eprintln!("Go version {} is not installed.", version);
+    let answer = terminal.ask("Would you like to install it? [Y/n/(a)lways/ne(v)er] ")?;
+    // End of input (Ctrl-D) is not a "yes".
+    if answer.is_empty() {
+        return Ok((Decision::Decline, None));
+    }
+    Ok(match answer.trim().to_lowercase().as_str() {
+        "" | "y" | "yes" => (Decision::Install, None),
+        "a" | "always" => (Decision::Install, Some(AutoInstall::Always)),
+        "v" | "never" => (Decision::Decline, Some(AutoInstall::Never)),
+        _ => (Decision::Decline, None),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct MockTerminal {
+        answer: &'static str,
+        asked: bool,
+    }
+
+    impl Terminal for MockTerminal {
+        fn ask(&mut self, _question: &str) -> io::Result<String> {
+            self.asked = true;
+            Ok(self.answer.to_string())
+        }
+    }
+
+    fn ask(answer: &'static str) -> (Decision, Option<AutoInstall>) {
+        let mut terminal = MockTerminal {
+            answer,
+            asked: false,
+        };
+        decide("1.22.3", AutoInstall::Prompt, Some(&mut terminal)).unwrap()
+    }
+
+    #[test]
+    fn answers_map_to_decisions() {
+        assert_eq!(ask("\n"), (Decision::Install, None));
+        assert_eq!(ask("Yes\n"), (Decision::Install, None));
+        assert_eq!(ask("n\n"), (Decision::Decline, None));
+        assert_eq!(ask(""), (Decision::Decline, None));
+        assert_eq!(
+            ask("always\n"),
+            (Decision::Install, Some(AutoInstall::Always))
+        );
+        assert_eq!(
+            ask("never\n"),
+            (Decision::Decline, Some(AutoInstall::Never))
+        );
+        assert_eq!(ask("v\n"), (Decision::Decline, Some(AutoInstall::Never)));
+        assert_eq!(ask("a\n"), (Decision::Install, Some(AutoInstall::Always)));
+        assert_eq!(ask("maybe\n"), (Decision::Decline, None));
+    }
+
+    #[test]
+    fn fixed_policies_never_ask() {
+        let mut terminal = MockTerminal {
+            answer: "n\n",
+            asked: false,
+        };
+
+        let always = decide("1.22.3", AutoInstall::Always, Some(&mut terminal)).unwrap();
+        let never = decide("1.22.3", AutoInstall::Never, Some(&mut terminal)).unwrap();
+
+        assert_eq!(always, (Decision::Install, None));
+        assert_eq!(never, (Decision::Decline, None));
+        assert!(!terminal.asked);
+    }
+
+    #[test]
+    fn prompt_without_terminal_cannot_ask() {
+        let result = decide("1.22.3", AutoInstall::Prompt, None).unwrap();
+
+        assert_eq!(result, (Decision::CannotAsk, None));
+    }
+}
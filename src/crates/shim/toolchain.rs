@@ -0,0 +1,167 @@
+use golta_core::gover::GoVersion;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// How the shim treats Go's own toolchain switching (`GOTOOLCHAIN`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ToolchainPlan {
+    /// No user setting: golta picks the version and the child gets `GOTOOLCHAIN=local`,
+    /// so Go never swaps in a toolchain from the module cache.
+    Managed,
+    /// `GOTOOLCHAIN=local`: golta picks the version, nothing to change.
+    Local,
+    /// `GOTOOLCHAIN=go1.x.y`: run exactly that version, as Go would.
+    Exact(String),
+    /// `auto`, `path`, `<name>+auto` or `<name>+path`: the user opted into Go's own
+    /// switching. The named toolchain (if any) is the starting point and `GOTOOLCHAIN`
+    /// is passed through untouched.
+    Switching(Option<String>),
+}
+
+impl ToolchainPlan {
+    /// Reads `GOTOOLCHAIN` from the environment, then from the `go env -w` file.
+    pub fn from_env(home_dir: &Path) -> Self {
+        let value = env::var("GOTOOLCHAIN")
+            .ok()
+            .filter(|v| !v.trim().is_empty())
+            .or_else(|| go_env_file(home_dir).and_then(|path| read_go_env_toolchain(&path)));
+        Self::parse(value.as_deref())
+    }
+
+    pub fn parse(value: Option<&str>) -> Self {
+        let Some(value) = value.map(str::trim).filter(|v| !v.is_empty()) else {
+            return Self::Managed;
+        };
+
+        let (name, mode) = match value {
+            "auto" | "path" => ("local", Some(value)),
+            _ => match value.split_once('+') {
+                Some((name, mode)) => (name, Some(mode)),
+                None => (value, None),
+            },
+        };
+
+        let version = if name == "local" {
+            None
+        } else {
+            named_version(name)
+        };
+
+        match (mode, version) {
+            (Some(_), version) => Self::Switching(version),
+            (None, Some(version)) => Self::Exact(version),
+            // `local`, or something Go itself will reject; let it report the error.
+            (None, None) => Self::Local,
+        }
+    }
+
+    /// The version that replaces golta's own resolution, if the setting names one.
+    pub fn version_override(&self) -> Option<&str> {
+        match self {
+            Self::Exact(version) | Self::Switching(Some(version)) => Some(version),
+            _ => None,
+        }
+    }
+
+    /// The `GOTOOLCHAIN` value to export to the child, if it has to change.
+    pub fn child_value(&self) -> Option<&'static str> {
+        match self {
+            Self::Managed | Self::Exact(_) => Some("local"),
+            Self::Local | Self::Switching(_) => None,
+        }
+    }
+}
+
+/// Turns a toolchain name such as `go1.22.3` into `1.22.3`.
+fn named_version(name: &str) -> Option<String> {
+    let version = name.strip_prefix("go")?;
+    GoVersion::parse(version).map(|_| version.to_string())
+}
+
+/// Locates the file written by `go env -w`: `$GOENV`, or `go/env` in the user config directory.
+fn go_env_file(home_dir: &Path) -> Option<PathBuf> {
+    if let Ok(goenv) = env::var("GOENV") {
+        return match goenv.as_str() {
+            "" => None,
+            "off" => None,
+            _ => Some(PathBuf::from(goenv)),
+        };
+    }
+    Some(user_config_dir(home_dir)?.join("go").join("env"))
+}
+
+#[cfg(target_os = "macos")]
+fn user_config_dir(home_dir: &Path) -> Option<PathBuf> {
+    Some(home_dir.join("Library").join("Application Support"))
+}
+
+#[cfg(windows)]
+fn user_config_dir(_home_dir: &Path) -> Option<PathBuf> {
+    env::var_os("AppData").map(PathBuf::from)
+}
+
+#[cfg(not(any(target_os = "macos", windows)))]
+fn user_config_dir(home_dir: &Path) -> Option<PathBuf> {
+    match env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) if !dir.is_empty() => Some(PathBuf::from(dir)),
+        _ => Some(home_dir.join(".config")),
+    }
+}
+
+fn read_go_env_toolchain(path: &Path) -> Option<String> {
+    let content = fs::read_to_string(path).ok()?;
+    content.lines().find_map(|line| {
+        let value = line.trim().strip_prefix("GOTOOLCHAIN=")?.trim();
+        (!value.is_empty()).then(|| value.to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn unset_means_golta_manages_the_toolchain() {
+        let plan = ToolchainPlan::parse(None);
+
+        assert_eq!(plan, ToolchainPlan::Managed);
+        assert_eq!(plan.version_override(), None);
+        assert_eq!(plan.child_value(), Some("local"));
+    }
+
+    #[test]
+    fn explicit_toolchain_overrides_resolution() {
+        let plan = ToolchainPlan::parse(Some("go1.22.3"));
+
+        assert_eq!(plan.version_override(), Some("1.22.3"));
+        assert_eq!(plan.child_value(), Some("local"));
+        assert_eq!(ToolchainPlan::parse(Some("local")), ToolchainPlan::Local);
+    }
+
+    #[test]
+    fn auto_and_path_opt_out() {
+        for value in ["auto", "path", "local+auto", "local+path"] {
+            let plan = ToolchainPlan::parse(Some(value));
+            assert_eq!(plan, ToolchainPlan::Switching(None), "{}", value);
+            assert_eq!(plan.child_value(), None);
+        }
+
+        let plan = ToolchainPlan::parse(Some("go1.21.0+auto"));
+        assert_eq!(plan.version_override(), Some("1.21.0"));
+        assert_eq!(plan.child_value(), None);
+    }
+
+    #[test]
+    fn reads_go_env_file() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("env");
+        fs::write(&path, "GOPROXY=direct\nGOTOOLCHAIN=go1.22.1+auto\n").unwrap();
+
+        assert_eq!(
+            read_go_env_toolchain(&path).as_deref(),
+            Some("go1.22.1+auto")
+        );
+    }
+}